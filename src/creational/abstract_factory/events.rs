@@ -0,0 +1,50 @@
+// Widgets tell the rest of the app what happened to them through events.
+// Callers register callbacks for the kinds of events they care about.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventKind {
+    Click,
+    Change,
+    Focus,
+    Blur,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    Click,
+    Change(String),
+    Focus,
+    Blur,
+}
+
+impl Event {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Event::Click => EventKind::Click,
+            Event::Change(_) => EventKind::Change,
+            Event::Focus => EventKind::Focus,
+            Event::Blur => EventKind::Blur,
+        }
+    }
+}
+
+pub type Callback = Box<dyn Fn(&Event)>;
+
+#[derive(Default)]
+pub struct Listeners {
+    callbacks: Vec<(EventKind, Callback)>,
+}
+
+impl Listeners {
+    pub fn add(&mut self, kind: EventKind, callback: Callback) {
+        self.callbacks.push((kind, callback));
+    }
+
+    pub fn emit(&self, event: &Event) {
+        for (kind, callback) in self.callbacks.iter() {
+            if *kind == event.kind() {
+                callback(event);
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+// Inputs hold what the user typed and check it against their validators.
+//
+// InputState is the part every platform's input has in common: the value, the validators
+// and the registered callbacks. Platform inputs keep one and only add their own looks.
+
+use std::fmt;
+
+use regex::Regex;
+
+use super::{Callback, Event, EventKind, Listeners};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationError {
+    NoMatch(String),
+    TooShort { min: usize },
+    TooLong { max: usize },
+    NotANumber,
+    OutOfRange { min: f64, max: f64 },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::NoMatch(pattern) => write!(f, "The value doesn't match {}", pattern),
+            ValidationError::TooShort { min } => write!(f, "The value needs at least {} characters", min),
+            ValidationError::TooLong { max } => write!(f, "The value can't be longer than {} characters", max),
+            ValidationError::NotANumber => write!(f, "The value is not a number"),
+            ValidationError::OutOfRange { min, max } => write!(f, "The value must be between {} and {}", min, max),
+        }
+    }
+}
+
+pub trait Validator {
+    fn validate(&self, value: &str) -> Result<(), ValidationError>;
+}
+
+// Matches accepts values that match a regular expression
+pub struct Matches {
+    pattern: Regex,
+}
+
+impl Matches {
+    pub fn new(pattern: &str) -> Result<Matches, regex::Error> {
+        Ok(Matches { pattern: Regex::new(pattern)? })
+    }
+}
+
+impl Validator for Matches {
+    fn validate(&self, value: &str) -> Result<(), ValidationError> {
+        if !self.pattern.is_match(value) {
+            return Err(ValidationError::NoMatch(self.pattern.to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+// Length accepts values with a number of characters between min and max
+pub struct Length {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl Validator for Length {
+    fn validate(&self, value: &str) -> Result<(), ValidationError> {
+        let length = value.chars().count();
+        if length < self.min {
+            return Err(ValidationError::TooShort { min: self.min });
+        }
+        if length > self.max {
+            return Err(ValidationError::TooLong { max: self.max });
+        }
+
+        Ok(())
+    }
+}
+
+// Numeric accepts numbers between min and max
+pub struct Numeric {
+    pub min: f64,
+    pub max: f64,
+}
+
+#[allow(dead_code)]
+impl Numeric {
+    pub fn any() -> Numeric {
+        Numeric { min: f64::NEG_INFINITY, max: f64::INFINITY }
+    }
+}
+
+impl Validator for Numeric {
+    fn validate(&self, value: &str) -> Result<(), ValidationError> {
+        let number: f64 = value.trim().parse().map_err(|_| ValidationError::NotANumber)?;
+        if number < self.min || number > self.max {
+            return Err(ValidationError::OutOfRange { min: self.min, max: self.max });
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct InputState {
+    value: String,
+    validators: Vec<Box<dyn Validator>>,
+    listeners: Listeners,
+}
+
+impl InputState {
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn set_value(&mut self, value: String) {
+        self.value = value;
+        self.listeners.emit(&Event::Change(self.value.clone()));
+    }
+
+    pub fn add_validator(&mut self, validator: Box<dyn Validator>) {
+        self.validators.push(validator);
+    }
+
+    // Returns every rule the current value breaks
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let errors: Vec<ValidationError> = self.validators
+            .iter()
+            .filter_map(|validator| validator.validate(&self.value).err())
+            .collect();
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(())
+    }
+
+    pub fn on(&mut self, kind: EventKind, callback: Callback) {
+        self.listeners.add(kind, callback);
+    }
+
+    pub fn emit(&self, event: Event) {
+        self.listeners.emit(&event);
+    }
+}
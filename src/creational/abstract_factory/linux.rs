@@ -0,0 +1,250 @@
+// Linux family, styled after GTK. Dropdowns are combo boxes.
+
+use super::{
+    Button, Callback, Checkbox, Dialog, Dropdown, Event, EventKind, Input, InputState, Label, Listeners, Slider, Style,
+    UIManager, ValidationError, Validator, Widget,
+};
+
+pub const STYLE: Style = Style {
+    open: '<',
+    close: '>',
+    check: '*',
+    knob: 'O',
+    corner: '#',
+    controls: "_ x",
+    controls_left: false,
+};
+
+struct LinuxButton {
+    label: String,
+    listeners: Listeners,
+}
+
+impl Widget for LinuxButton {
+    fn render(&self) -> String {
+        STYLE.button(&self.label)
+    }
+}
+
+impl Button for LinuxButton {
+    fn click(&self) {
+        println!("Hello from GTK");
+        self.listeners.emit(&Event::Click);
+    }
+
+    fn set_label(&mut self, label: &str) {
+        self.label = label.to_string();
+        self.listeners.emit(&Event::Change(self.label.clone()));
+    }
+
+    fn on_focus(&self) {
+        println!("GTK button is now focused!");
+        self.listeners.emit(&Event::Focus);
+    }
+
+    fn on_blur(&self) {
+        println!("Goodbye GTK button!");
+        self.listeners.emit(&Event::Blur);
+    }
+
+    fn on(&mut self, kind: EventKind, callback: Callback) {
+        self.listeners.add(kind, callback);
+    }
+}
+
+struct LinuxInput {
+    state: InputState,
+}
+
+impl Widget for LinuxInput {
+    fn render(&self) -> String {
+        STYLE.input(self.state.value())
+    }
+}
+
+impl Input for LinuxInput {
+    fn on_input(&mut self, value: String) {
+        println!("GTK is typing...");
+        self.state.set_value(value);
+    }
+
+    fn click(&self) {
+        self.state.emit(Event::Click);
+    }
+
+    fn on_focus(&self) {
+        println!("GTK is now focused!");
+        self.state.emit(Event::Focus);
+    }
+
+    fn on_blur(&self) {
+        println!("Goodbye GTK!");
+        self.state.emit(Event::Blur);
+    }
+
+    fn value(&self) -> &str {
+        self.state.value()
+    }
+
+    fn add_validator(&mut self, validator: Box<dyn Validator>) {
+        self.state.add_validator(validator);
+    }
+
+    fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        self.state.validate()
+    }
+
+    fn on(&mut self, kind: EventKind, callback: Callback) {
+        self.state.on(kind, callback);
+    }
+}
+
+struct LinuxCheckbox {
+    label: String,
+    checked: bool,
+}
+
+impl Widget for LinuxCheckbox {
+    fn render(&self) -> String {
+        STYLE.checkbox(&self.label, self.checked)
+    }
+}
+
+impl Checkbox for LinuxCheckbox {
+    fn toggle(&mut self) {
+        self.checked = !self.checked;
+        println!("GTK checkbox {} is {}", self.label, if self.checked { "checked" } else { "unchecked" });
+    }
+
+    fn is_checked(&self) -> bool {
+        self.checked
+    }
+}
+
+struct LinuxDropdown {
+    options: Vec<String>,
+    selected: Option<usize>,
+}
+
+impl Widget for LinuxDropdown {
+    fn render(&self) -> String {
+        STYLE.dropdown(self.selected())
+    }
+}
+
+impl Dropdown for LinuxDropdown {
+    fn select(&mut self, option: &str) -> bool {
+        let Some(index) = self.options.iter().position(|candidate| candidate == option) else {
+            return false;
+        };
+
+        self.selected = Some(index);
+        println!("GTK combo box picked {}", option);
+
+        true
+    }
+
+    fn selected(&self) -> Option<&str> {
+        self.selected.map(|index| self.options[index].as_str())
+    }
+}
+
+struct LinuxLabel {
+    text: String,
+}
+
+impl Widget for LinuxLabel {
+    fn render(&self) -> String {
+        self.text.clone()
+    }
+}
+
+impl Label for LinuxLabel {
+    fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn show(&self) {
+        println!("GTK label: {}", self.text);
+    }
+}
+
+struct LinuxSlider {
+    min: i32,
+    max: i32,
+    value: i32,
+}
+
+impl Widget for LinuxSlider {
+    fn render(&self) -> String {
+        STYLE.slider(self.min, self.max, self.value)
+    }
+}
+
+impl Slider for LinuxSlider {
+    fn slide(&mut self, value: i32) {
+        self.value = value.clamp(self.min, self.max);
+        println!("GTK slider moved to {}", self.value);
+    }
+
+    fn value(&self) -> i32 {
+        self.value
+    }
+}
+
+struct LinuxDialog {
+    title: String,
+}
+
+impl Widget for LinuxDialog {
+    fn render(&self) -> String {
+        STYLE.dialog(&self.title)
+    }
+}
+
+impl Dialog for LinuxDialog {
+    fn open(&self) {
+        println!("GTK dialog {} opens", self.title);
+    }
+
+    fn close(&self) {
+        println!("GTK dialog {} closes", self.title);
+    }
+}
+
+pub struct LinuxUIManager {}
+impl UIManager for LinuxUIManager {
+    fn style(&self) -> &'static Style {
+        &STYLE
+    }
+
+    fn create_button(&self, label: &str) -> Box<dyn Button> {
+        Box::new(LinuxButton{ label: label.to_string(), listeners: Listeners::default() })
+    }
+
+    fn create_input(&self) -> Box<dyn Input> {
+        Box::new(LinuxInput{ state: InputState::default() })
+    }
+
+    fn create_checkbox(&self, label: &str) -> Box<dyn Checkbox> {
+        Box::new(LinuxCheckbox{ label: label.to_string(), checked: false })
+    }
+
+    fn create_dropdown(&self, options: &[&str]) -> Box<dyn Dropdown> {
+        Box::new(LinuxDropdown{ options: options.iter().map(|option| option.to_string()).collect(), selected: None })
+    }
+
+    fn create_label(&self, text: &str) -> Box<dyn Label> {
+        Box::new(LinuxLabel{ text: text.to_string() })
+    }
+
+    fn create_slider(&self, min: i32, max: i32) -> Box<dyn Slider> {
+        let (min, max) = (min.min(max), min.max(max));
+
+        Box::new(LinuxSlider{ min, max, value: min })
+    }
+
+    fn create_dialog(&self, title: &str) -> Box<dyn Dialog> {
+        Box::new(LinuxDialog{ title: title.to_string() })
+    }
+}
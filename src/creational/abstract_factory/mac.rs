@@ -0,0 +1,250 @@
+// Mac family, styled after Cupertino. Dialogs slide down as sheets.
+
+use super::{
+    Button, Callback, Checkbox, Dialog, Dropdown, Event, EventKind, Input, InputState, Label, Listeners, Slider, Style,
+    UIManager, ValidationError, Validator, Widget,
+};
+
+pub const STYLE: Style = Style {
+    open: '(',
+    close: ')',
+    check: 'v',
+    knob: 'o',
+    corner: '.',
+    controls: "o o o",
+    controls_left: true,
+};
+
+struct MacButton {
+    label: String,
+    listeners: Listeners,
+}
+
+impl Widget for MacButton {
+    fn render(&self) -> String {
+        STYLE.button(&self.label)
+    }
+}
+
+impl Button for MacButton {
+    fn click(&self) {
+        println!("Hello Cupertino");
+        self.listeners.emit(&Event::Click);
+    }
+
+    fn set_label(&mut self, label: &str) {
+        self.label = label.to_string();
+        self.listeners.emit(&Event::Change(self.label.clone()));
+    }
+
+    fn on_focus(&self) {
+        println!("Cupertino button is now focused!");
+        self.listeners.emit(&Event::Focus);
+    }
+
+    fn on_blur(&self) {
+        println!("Goodbye Cupertino button!");
+        self.listeners.emit(&Event::Blur);
+    }
+
+    fn on(&mut self, kind: EventKind, callback: Callback) {
+        self.listeners.add(kind, callback);
+    }
+}
+
+struct MacInput {
+    state: InputState,
+}
+
+impl Widget for MacInput {
+    fn render(&self) -> String {
+        STYLE.input(self.state.value())
+    }
+}
+
+impl Input for MacInput {
+    fn on_input(&mut self, value: String) {
+        println!("Cupertino is typing...");
+        self.state.set_value(value);
+    }
+
+    fn click(&self) {
+        self.state.emit(Event::Click);
+    }
+
+    fn on_focus(&self) {
+        println!("Cupertino is now focused!");
+        self.state.emit(Event::Focus);
+    }
+
+    fn on_blur(&self) {
+        println!("Goodbye Cupertino!");
+        self.state.emit(Event::Blur);
+    }
+
+    fn value(&self) -> &str {
+        self.state.value()
+    }
+
+    fn add_validator(&mut self, validator: Box<dyn Validator>) {
+        self.state.add_validator(validator);
+    }
+
+    fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        self.state.validate()
+    }
+
+    fn on(&mut self, kind: EventKind, callback: Callback) {
+        self.state.on(kind, callback);
+    }
+}
+
+struct MacCheckbox {
+    label: String,
+    checked: bool,
+}
+
+impl Widget for MacCheckbox {
+    fn render(&self) -> String {
+        STYLE.checkbox(&self.label, self.checked)
+    }
+}
+
+impl Checkbox for MacCheckbox {
+    fn toggle(&mut self) {
+        self.checked = !self.checked;
+        println!("Cupertino checkbox {} is {}", self.label, if self.checked { "checked" } else { "unchecked" });
+    }
+
+    fn is_checked(&self) -> bool {
+        self.checked
+    }
+}
+
+struct MacDropdown {
+    options: Vec<String>,
+    selected: Option<usize>,
+}
+
+impl Widget for MacDropdown {
+    fn render(&self) -> String {
+        STYLE.dropdown(self.selected())
+    }
+}
+
+impl Dropdown for MacDropdown {
+    fn select(&mut self, option: &str) -> bool {
+        let Some(index) = self.options.iter().position(|candidate| candidate == option) else {
+            return false;
+        };
+
+        self.selected = Some(index);
+        println!("Cupertino pop-up button picked {}", option);
+
+        true
+    }
+
+    fn selected(&self) -> Option<&str> {
+        self.selected.map(|index| self.options[index].as_str())
+    }
+}
+
+struct MacLabel {
+    text: String,
+}
+
+impl Widget for MacLabel {
+    fn render(&self) -> String {
+        self.text.clone()
+    }
+}
+
+impl Label for MacLabel {
+    fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn show(&self) {
+        println!("Cupertino label: {}", self.text);
+    }
+}
+
+struct MacSlider {
+    min: i32,
+    max: i32,
+    value: i32,
+}
+
+impl Widget for MacSlider {
+    fn render(&self) -> String {
+        STYLE.slider(self.min, self.max, self.value)
+    }
+}
+
+impl Slider for MacSlider {
+    fn slide(&mut self, value: i32) {
+        self.value = value.clamp(self.min, self.max);
+        println!("Cupertino slider moved to {}", self.value);
+    }
+
+    fn value(&self) -> i32 {
+        self.value
+    }
+}
+
+struct MacDialog {
+    title: String,
+}
+
+impl Widget for MacDialog {
+    fn render(&self) -> String {
+        STYLE.dialog(&self.title)
+    }
+}
+
+impl Dialog for MacDialog {
+    fn open(&self) {
+        println!("Cupertino sheet {} opens", self.title);
+    }
+
+    fn close(&self) {
+        println!("Cupertino sheet {} closes", self.title);
+    }
+}
+
+pub struct MacUIManager {}
+impl UIManager for MacUIManager {
+    fn style(&self) -> &'static Style {
+        &STYLE
+    }
+
+    fn create_button(&self, label: &str) -> Box<dyn Button> {
+        Box::new(MacButton{ label: label.to_string(), listeners: Listeners::default() })
+    }
+
+    fn create_input(&self) -> Box<dyn Input> {
+        Box::new(MacInput{ state: InputState::default() })
+    }
+
+    fn create_checkbox(&self, label: &str) -> Box<dyn Checkbox> {
+        Box::new(MacCheckbox{ label: label.to_string(), checked: false })
+    }
+
+    fn create_dropdown(&self, options: &[&str]) -> Box<dyn Dropdown> {
+        Box::new(MacDropdown{ options: options.iter().map(|option| option.to_string()).collect(), selected: None })
+    }
+
+    fn create_label(&self, text: &str) -> Box<dyn Label> {
+        Box::new(MacLabel{ text: text.to_string() })
+    }
+
+    fn create_slider(&self, min: i32, max: i32) -> Box<dyn Slider> {
+        let (min, max) = (min.min(max), min.max(max));
+
+        Box::new(MacSlider{ min, max, value: min })
+    }
+
+    fn create_dialog(&self, title: &str) -> Box<dyn Dialog> {
+        Box::new(MacDialog{ title: title.to_string() })
+    }
+}
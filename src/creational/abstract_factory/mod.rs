@@ -0,0 +1,199 @@
+// Abstract factory pattern is an upgraded version of factory method. In layman terms,
+// abstract factory method introduces 'master' factory that calls other factory for client.
+//
+// In this example, we are building a cross-platform GUI app. Your app runs on Windows and
+// Mac that have their own quirks related to UI elements such as buttons and inputs. Fortunately To
+// return the correct 'UI Kit'
+//
+// The app now runs on Linux too, with its own GTK-style kit. Instead of choosing the kit by hand,
+// the app detects the platform it runs on (see `platform.rs`), and themes such as dark mode
+// can be layered on top of any kit from a registry (see `theme.rs`).
+//
+// Besides buttons and inputs, every kit offers checkboxes, dropdowns, labels, sliders and
+// dialogs, so a whole form can be built from one factory. Each family lives in its own file.
+//
+// Widgets can also be drawn as text in a frame without a display, each family with
+// its own style (see `render.rs`).
+//
+// Inputs keep what the user typed and check it against validators (see `input.rs`),
+// and callers can react to clicks, changes, focus and blur through callbacks (see `events.rs`).
+// Buttons and inputs both emit every kind of event.
+
+mod events;
+mod input;
+mod linux;
+mod mac;
+mod platform;
+mod render;
+mod theme;
+mod windows;
+
+use std::env;
+use std::error::Error;
+use std::fmt;
+
+#[allow(unused_imports)]
+pub use events::{Callback, Event, EventKind, Listeners};
+#[allow(unused_imports)]
+pub use input::{InputState, Length, Matches, Numeric, ValidationError, Validator};
+#[allow(unused_imports)]
+pub use platform::{Platform, PLATFORM_VAR};
+#[allow(unused_imports)]
+pub use render::{Frame, Style, Widget};
+#[allow(unused_imports)]
+pub use theme::{ThemeRegistry, ThemedUIManager};
+pub use linux::LinuxUIManager;
+pub use mac::MacUIManager;
+pub use windows::WindowsUIManager;
+
+pub const THEME_VAR: &str = "UI_THEME";
+
+// Button is an abstract UI component that every OS have
+pub trait Button: Widget {
+    fn click(&self);
+    // Changing the label is the button's change event
+    fn set_label(&mut self, label: &str);
+    fn on_focus(&self);
+    fn on_blur(&self);
+    fn on(&mut self, kind: EventKind, callback: Callback);
+}
+
+// Input is another abstract UI component that every OS have
+pub trait Input: Widget {
+    fn on_input(&mut self, value: String);
+    fn click(&self);
+    fn on_focus(&self);
+    fn on_blur(&self);
+    fn value(&self) -> &str;
+    fn add_validator(&mut self, validator: Box<dyn Validator>);
+    fn validate(&self) -> Result<(), Vec<ValidationError>>;
+    fn on(&mut self, kind: EventKind, callback: Callback);
+}
+
+// Checkbox can be ticked on and off
+pub trait Checkbox: Widget {
+    fn toggle(&mut self);
+    fn is_checked(&self) -> bool;
+}
+
+// Dropdown lets the user pick one of its options
+pub trait Dropdown: Widget {
+    fn select(&mut self, option: &str) -> bool;
+    fn selected(&self) -> Option<&str>;
+}
+
+// Label shows a piece of text next to other components
+pub trait Label: Widget {
+    fn text(&self) -> &str;
+    fn show(&self);
+}
+
+// Slider picks a number between its minimum and maximum
+pub trait Slider: Widget {
+    fn slide(&mut self, value: i32);
+    fn value(&self) -> i32;
+}
+
+// Dialog asks for the user's attention on top of everything else
+pub trait Dialog: Widget {
+    fn open(&self);
+    fn close(&self);
+}
+
+// UIManager is an abstract factory interface
+pub trait UIManager {
+    fn style(&self) -> &'static Style;
+
+    fn create_frame<'a>(&self, title: &str) -> Frame<'a> {
+        Frame::new(title, self.style())
+    }
+
+    fn create_button(&self, label: &str) -> Box<dyn Button>;
+    fn create_input(&self) -> Box<dyn Input>;
+    fn create_checkbox(&self, label: &str) -> Box<dyn Checkbox>;
+    fn create_dropdown(&self, options: &[&str]) -> Box<dyn Dropdown>;
+    fn create_label(&self, text: &str) -> Box<dyn Label>;
+    // Bounds given the wrong way round are swapped
+    fn create_slider(&self, min: i32, max: i32) -> Box<dyn Slider>;
+    fn create_dialog(&self, title: &str) -> Box<dyn Dialog>;
+}
+
+#[derive(Debug)]
+pub enum UIError {
+    UnknownPlatform(String),
+    UnknownTheme(String),
+}
+
+impl fmt::Display for UIError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UIError::UnknownPlatform(platform) => write!(f, "There is no UI kit for {}", platform),
+            UIError::UnknownTheme(theme) => write!(f, "Theme {} is not registered", theme),
+        }
+    }
+}
+
+impl Error for UIError {}
+
+// AppUIManager is the 'master' factory that encapsulates specialized factories
+pub struct AppUIManager {
+    pub os_manager: Box<dyn UIManager>,
+}
+
+#[allow(dead_code)]
+impl AppUIManager {
+    pub fn new(platform: Platform) -> AppUIManager {
+        AppUIManager { os_manager: platform.manager() }
+    }
+
+    pub fn with_theme(platform: Platform, theme: &str, themes: &ThemeRegistry) -> Result<AppUIManager, UIError> {
+        let os_manager = themes
+            .apply(theme, platform.manager())
+            .ok_or_else(|| UIError::UnknownTheme(theme.to_string()))?;
+
+        Ok(AppUIManager { os_manager })
+    }
+
+    // Picks the kit of the platform the app runs on, along with the theme
+    // in `UI_THEME` if there is one
+    pub fn detect(themes: &ThemeRegistry) -> Result<AppUIManager, UIError> {
+        let platform = Platform::detect()?;
+
+        match env::var(THEME_VAR) {
+            Ok(theme) => AppUIManager::with_theme(platform, &theme, themes),
+            Err(_) => Ok(AppUIManager::new(platform)),
+        }
+    }
+
+    pub fn create_frame<'a>(&self, title: &str) -> Frame<'a> {
+        self.os_manager.create_frame(title)
+    }
+
+    pub fn create_button(&self, label: &str) -> Box<dyn Button> {
+        self.os_manager.create_button(label)
+    }
+
+    pub fn create_input(&self) -> Box<dyn Input> {
+        self.os_manager.create_input()
+    }
+
+    pub fn create_checkbox(&self, label: &str) -> Box<dyn Checkbox> {
+        self.os_manager.create_checkbox(label)
+    }
+
+    pub fn create_dropdown(&self, options: &[&str]) -> Box<dyn Dropdown> {
+        self.os_manager.create_dropdown(options)
+    }
+
+    pub fn create_label(&self, text: &str) -> Box<dyn Label> {
+        self.os_manager.create_label(text)
+    }
+
+    pub fn create_slider(&self, min: i32, max: i32) -> Box<dyn Slider> {
+        self.os_manager.create_slider(min, max)
+    }
+
+    pub fn create_dialog(&self, title: &str) -> Box<dyn Dialog> {
+        self.os_manager.create_dialog(title)
+    }
+}
@@ -0,0 +1,58 @@
+// Platform tells which UI family the app should use. It's detected from the OS the app
+// was compiled for, unless the `UI_PLATFORM` environment variable says otherwise.
+
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+
+use super::{LinuxUIManager, MacUIManager, UIError, UIManager, WindowsUIManager};
+
+pub const PLATFORM_VAR: &str = "UI_PLATFORM";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Platform {
+    Windows,
+    Mac,
+    Linux,
+}
+
+impl Platform {
+    pub fn detect() -> Result<Platform, UIError> {
+        match env::var(PLATFORM_VAR) {
+            Ok(platform) => platform.parse(),
+            Err(_) => env::consts::OS.parse(),
+        }
+    }
+
+    // Returns the factory of this platform's UI family
+    pub fn manager(&self) -> Box<dyn UIManager> {
+        match self {
+            Platform::Windows => Box::new(WindowsUIManager{}),
+            Platform::Mac => Box::new(MacUIManager{}),
+            Platform::Linux => Box::new(LinuxUIManager{}),
+        }
+    }
+}
+
+impl FromStr for Platform {
+    type Err = UIError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "windows" => Ok(Platform::Windows),
+            "mac" | "macos" => Ok(Platform::Mac),
+            "linux" => Ok(Platform::Linux),
+            _ => Err(UIError::UnknownPlatform(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Platform::Windows => write!(f, "windows"),
+            Platform::Mac => write!(f, "mac"),
+            Platform::Linux => write!(f, "linux"),
+        }
+    }
+}
@@ -0,0 +1,113 @@
+// Widgets can be drawn without a display. Every widget renders itself into a line of text,
+// and a Frame lays those lines out inside a window, so the whole UI ends up as a plain string
+// that can be printed or compared against a snapshot.
+//
+// Each platform family brings its own Style: the brackets around buttons and fields,
+// the mark of a ticked checkbox, the knob of a slider and the look of the window itself.
+
+// Widget is anything that can be drawn inside a frame
+pub trait Widget {
+    fn render(&self) -> String;
+}
+
+pub struct Style {
+    pub open: char,
+    pub close: char,
+    pub check: char,
+    pub knob: char,
+    pub corner: char,
+    // Window controls such as close and minimize, drawn on the title bar
+    pub controls: &'static str,
+    pub controls_left: bool,
+}
+
+const FIELD_WIDTH: usize = 12;
+const SLIDER_WIDTH: usize = 10;
+
+impl Style {
+    pub fn button(&self, label: &str) -> String {
+        format!("{}  {}  {}", self.open, label, self.close)
+    }
+
+    pub fn input(&self, value: &str) -> String {
+        format!("{}{:_<width$}{}", self.open, value, self.close, width = FIELD_WIDTH)
+    }
+
+    pub fn checkbox(&self, label: &str, checked: bool) -> String {
+        format!("[{}] {}", if checked { self.check } else { ' ' }, label)
+    }
+
+    pub fn dropdown(&self, selected: Option<&str>) -> String {
+        format!("{} {:<width$} v {}", self.open, selected.unwrap_or("-"), self.close, width = FIELD_WIDTH - 4)
+    }
+
+    pub fn slider(&self, min: i32, max: i32, value: i32) -> String {
+        // Widened so the span of the whole i32 range doesn't overflow
+        let span = (max as i64 - min as i64).max(1);
+        let position = ((value as i64 - min as i64).clamp(0, span) * SLIDER_WIDTH as i64 / span) as usize;
+        let track: String = (0..=SLIDER_WIDTH)
+            .map(|i| if i == position { self.knob } else if i < position { '=' } else { '-' })
+            .collect();
+
+        format!("{} |{}| {}", min, track, max)
+    }
+
+    pub fn dialog(&self, title: &str) -> String {
+        format!("{0}{0} {1} {0}{0}", self.corner, title)
+    }
+}
+
+// Frame is a window that stacks rows of widgets on top of each other.
+// Widgets on the same row are placed side by side.
+pub struct Frame<'a> {
+    title: String,
+    style: &'static Style,
+    rows: Vec<Vec<&'a dyn Widget>>,
+}
+
+#[allow(dead_code)]
+impl<'a> Frame<'a> {
+    pub fn new(title: &str, style: &'static Style) -> Frame<'a> {
+        Frame { title: title.to_string(), style, rows: vec![] }
+    }
+
+    pub fn add(&mut self, widget: &'a dyn Widget) -> &mut Frame<'a> {
+        self.rows.push(vec![widget]);
+
+        self
+    }
+
+    pub fn add_row(&mut self, widgets: &[&'a dyn Widget]) -> &mut Frame<'a> {
+        self.rows.push(widgets.to_vec());
+
+        self
+    }
+
+    pub fn render(&self) -> String {
+        let style = self.style;
+        let lines: Vec<String> = self.rows
+            .iter()
+            .map(|row| row.iter().map(|widget| widget.render()).collect::<Vec<_>>().join("  "))
+            .collect();
+
+        let title = if style.controls_left {
+            format!("{}  {}", style.controls, self.title)
+        } else {
+            format!("{}  {}", self.title, style.controls)
+        };
+        let width = lines.iter().chain([&title]).map(|line| line.chars().count()).max().unwrap_or(0);
+        let border = format!("{}{}{}", style.corner, "-".repeat(width + 2), style.corner);
+
+        let title = if style.controls_left {
+            format!("{:<width$}", title)
+        } else {
+            format!("{}{:>pad$}", self.title, style.controls, pad = width - self.title.chars().count())
+        };
+
+        let mut frame = vec![border.clone(), format!("| {} |", title), border.clone()];
+        frame.extend(lines.iter().map(|line| format!("| {:<width$} |", line)));
+        frame.push(border);
+
+        frame.join("\n")
+    }
+}
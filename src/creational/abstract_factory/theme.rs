@@ -0,0 +1,296 @@
+// Themes such as dark mode or high contrast restyle whatever platform the app runs on.
+//
+// A theme wraps the platform's factory and decorates every widget it creates, so the
+// platform families don't need to know about themes at all. Themes live in a registry
+// by name, which means new ones can be plugged in at runtime without touching the
+// platform families.
+
+use std::collections::HashMap;
+
+use super::{
+    Button, Callback, Checkbox, Dialog, Dropdown, EventKind, Input, Label, Slider, Style, UIManager, ValidationError,
+    Validator, Widget,
+};
+
+type ThemeFactory = Box<dyn Fn(Box<dyn UIManager>) -> Box<dyn UIManager>>;
+
+pub struct ThemeRegistry {
+    themes: HashMap<String, ThemeFactory>,
+}
+
+#[allow(dead_code)]
+impl ThemeRegistry {
+    pub fn new() -> ThemeRegistry {
+        ThemeRegistry { themes: HashMap::new() }
+    }
+
+    pub fn register<F>(&mut self, name: &str, theme: F) -> &mut ThemeRegistry
+    where
+        F: Fn(Box<dyn UIManager>) -> Box<dyn UIManager> + 'static,
+    {
+        self.themes.insert(name.to_string(), Box::new(theme));
+
+        self
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.themes.keys().map(|name| name.as_str()).collect();
+        names.sort();
+
+        names
+    }
+
+    // Applies the theme on top of the platform's factory
+    pub fn apply(&self, name: &str, manager: Box<dyn UIManager>) -> Option<Box<dyn UIManager>> {
+        self.themes.get(name).map(|theme| theme(manager))
+    }
+}
+
+impl Default for ThemeRegistry {
+    fn default() -> Self {
+        let mut registry = ThemeRegistry::new();
+
+        registry
+            .register("dark", |manager| Box::new(ThemedUIManager { theme: "dark", manager }))
+            .register("high-contrast", |manager| Box::new(ThemedUIManager { theme: "high contrast", manager }));
+
+        registry
+    }
+}
+
+// ThemedUIManager decorates the widgets of another factory with a theme.
+// Themes only change colors, so the widgets are drawn the way their platform draws them.
+pub struct ThemedUIManager {
+    pub theme: &'static str,
+    pub manager: Box<dyn UIManager>,
+}
+
+impl UIManager for ThemedUIManager {
+    fn style(&self) -> &'static Style {
+        self.manager.style()
+    }
+
+    fn create_button(&self, label: &str) -> Box<dyn Button> {
+        Box::new(ThemedButton { theme: self.theme, button: self.manager.create_button(label) })
+    }
+
+    fn create_input(&self) -> Box<dyn Input> {
+        Box::new(ThemedInput { theme: self.theme, input: self.manager.create_input() })
+    }
+
+    fn create_checkbox(&self, label: &str) -> Box<dyn Checkbox> {
+        Box::new(ThemedCheckbox { theme: self.theme, checkbox: self.manager.create_checkbox(label) })
+    }
+
+    fn create_dropdown(&self, options: &[&str]) -> Box<dyn Dropdown> {
+        Box::new(ThemedDropdown { theme: self.theme, dropdown: self.manager.create_dropdown(options) })
+    }
+
+    fn create_label(&self, text: &str) -> Box<dyn Label> {
+        Box::new(ThemedLabel { theme: self.theme, label: self.manager.create_label(text) })
+    }
+
+    fn create_slider(&self, min: i32, max: i32) -> Box<dyn Slider> {
+        Box::new(ThemedSlider { theme: self.theme, slider: self.manager.create_slider(min, max) })
+    }
+
+    fn create_dialog(&self, title: &str) -> Box<dyn Dialog> {
+        Box::new(ThemedDialog { theme: self.theme, dialog: self.manager.create_dialog(title) })
+    }
+}
+
+struct ThemedButton {
+    theme: &'static str,
+    button: Box<dyn Button>,
+}
+
+impl Widget for ThemedButton {
+    fn render(&self) -> String {
+        self.button.render()
+    }
+}
+
+impl Button for ThemedButton {
+    fn click(&self) {
+        print!("[{}] ", self.theme);
+        self.button.click();
+    }
+
+    fn set_label(&mut self, label: &str) {
+        self.button.set_label(label);
+    }
+
+    fn on_focus(&self) {
+        print!("[{}] ", self.theme);
+        self.button.on_focus();
+    }
+
+    fn on_blur(&self) {
+        print!("[{}] ", self.theme);
+        self.button.on_blur();
+    }
+
+    fn on(&mut self, kind: EventKind, callback: Callback) {
+        self.button.on(kind, callback);
+    }
+}
+
+struct ThemedInput {
+    theme: &'static str,
+    input: Box<dyn Input>,
+}
+
+impl Widget for ThemedInput {
+    fn render(&self) -> String {
+        self.input.render()
+    }
+}
+
+impl Input for ThemedInput {
+    fn on_input(&mut self, value: String) {
+        print!("[{}] ", self.theme);
+        self.input.on_input(value);
+    }
+
+    fn click(&self) {
+        print!("[{}] ", self.theme);
+        self.input.click();
+    }
+
+    fn on_focus(&self) {
+        print!("[{}] ", self.theme);
+        self.input.on_focus();
+    }
+
+    fn on_blur(&self) {
+        print!("[{}] ", self.theme);
+        self.input.on_blur();
+    }
+
+    fn value(&self) -> &str {
+        self.input.value()
+    }
+
+    fn add_validator(&mut self, validator: Box<dyn Validator>) {
+        self.input.add_validator(validator);
+    }
+
+    fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        self.input.validate()
+    }
+
+    fn on(&mut self, kind: EventKind, callback: Callback) {
+        self.input.on(kind, callback);
+    }
+}
+
+struct ThemedCheckbox {
+    theme: &'static str,
+    checkbox: Box<dyn Checkbox>,
+}
+
+impl Widget for ThemedCheckbox {
+    fn render(&self) -> String {
+        self.checkbox.render()
+    }
+}
+
+impl Checkbox for ThemedCheckbox {
+    fn toggle(&mut self) {
+        print!("[{}] ", self.theme);
+        self.checkbox.toggle();
+    }
+
+    fn is_checked(&self) -> bool {
+        self.checkbox.is_checked()
+    }
+}
+
+struct ThemedDropdown {
+    theme: &'static str,
+    dropdown: Box<dyn Dropdown>,
+}
+
+impl Widget for ThemedDropdown {
+    fn render(&self) -> String {
+        self.dropdown.render()
+    }
+}
+
+impl Dropdown for ThemedDropdown {
+    fn select(&mut self, option: &str) -> bool {
+        print!("[{}] ", self.theme);
+        self.dropdown.select(option)
+    }
+
+    fn selected(&self) -> Option<&str> {
+        self.dropdown.selected()
+    }
+}
+
+struct ThemedLabel {
+    theme: &'static str,
+    label: Box<dyn Label>,
+}
+
+impl Widget for ThemedLabel {
+    fn render(&self) -> String {
+        self.label.render()
+    }
+}
+
+impl Label for ThemedLabel {
+    fn text(&self) -> &str {
+        self.label.text()
+    }
+
+    fn show(&self) {
+        print!("[{}] ", self.theme);
+        self.label.show();
+    }
+}
+
+struct ThemedSlider {
+    theme: &'static str,
+    slider: Box<dyn Slider>,
+}
+
+impl Widget for ThemedSlider {
+    fn render(&self) -> String {
+        self.slider.render()
+    }
+}
+
+impl Slider for ThemedSlider {
+    fn slide(&mut self, value: i32) {
+        print!("[{}] ", self.theme);
+        self.slider.slide(value);
+    }
+
+    fn value(&self) -> i32 {
+        self.slider.value()
+    }
+}
+
+struct ThemedDialog {
+    theme: &'static str,
+    dialog: Box<dyn Dialog>,
+}
+
+impl Widget for ThemedDialog {
+    fn render(&self) -> String {
+        self.dialog.render()
+    }
+}
+
+impl Dialog for ThemedDialog {
+    fn open(&self) {
+        print!("[{}] ", self.theme);
+        self.dialog.open();
+    }
+
+    fn close(&self) {
+        print!("[{}] ", self.theme);
+        self.dialog.close();
+    }
+}
@@ -0,0 +1,250 @@
+// Windows family, styled after Fluent
+
+use super::{
+    Button, Callback, Checkbox, Dialog, Dropdown, Event, EventKind, Input, InputState, Label, Listeners, Slider, Style,
+    UIManager, ValidationError, Validator, Widget,
+};
+
+pub const STYLE: Style = Style {
+    open: '[',
+    close: ']',
+    check: 'X',
+    knob: '#',
+    corner: '+',
+    controls: "_ [] X",
+    controls_left: false,
+};
+
+struct WindowsButton {
+    label: String,
+    listeners: Listeners,
+}
+
+impl Widget for WindowsButton {
+    fn render(&self) -> String {
+        STYLE.button(&self.label)
+    }
+}
+
+impl Button for WindowsButton {
+    fn click(&self) {
+        println!("Hello from Fluent");
+        self.listeners.emit(&Event::Click);
+    }
+
+    fn set_label(&mut self, label: &str) {
+        self.label = label.to_string();
+        self.listeners.emit(&Event::Change(self.label.clone()));
+    }
+
+    fn on_focus(&self) {
+        println!("Fluent button is now focused!");
+        self.listeners.emit(&Event::Focus);
+    }
+
+    fn on_blur(&self) {
+        println!("Goodbye Fluent button!");
+        self.listeners.emit(&Event::Blur);
+    }
+
+    fn on(&mut self, kind: EventKind, callback: Callback) {
+        self.listeners.add(kind, callback);
+    }
+}
+
+struct WindowsInput {
+    state: InputState,
+}
+
+impl Widget for WindowsInput {
+    fn render(&self) -> String {
+        STYLE.input(self.state.value())
+    }
+}
+
+impl Input for WindowsInput {
+    fn on_input(&mut self, value: String) {
+        println!("Fluent is typing...");
+        self.state.set_value(value);
+    }
+
+    fn click(&self) {
+        self.state.emit(Event::Click);
+    }
+
+    fn on_focus(&self) {
+        println!("Fluent is now focused!");
+        self.state.emit(Event::Focus);
+    }
+
+    fn on_blur(&self) {
+        println!("Goodbye Fluent!");
+        self.state.emit(Event::Blur);
+    }
+
+    fn value(&self) -> &str {
+        self.state.value()
+    }
+
+    fn add_validator(&mut self, validator: Box<dyn Validator>) {
+        self.state.add_validator(validator);
+    }
+
+    fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        self.state.validate()
+    }
+
+    fn on(&mut self, kind: EventKind, callback: Callback) {
+        self.state.on(kind, callback);
+    }
+}
+
+struct WindowsCheckbox {
+    label: String,
+    checked: bool,
+}
+
+impl Widget for WindowsCheckbox {
+    fn render(&self) -> String {
+        STYLE.checkbox(&self.label, self.checked)
+    }
+}
+
+impl Checkbox for WindowsCheckbox {
+    fn toggle(&mut self) {
+        self.checked = !self.checked;
+        println!("Fluent checkbox {} is {}", self.label, if self.checked { "checked" } else { "unchecked" });
+    }
+
+    fn is_checked(&self) -> bool {
+        self.checked
+    }
+}
+
+struct WindowsDropdown {
+    options: Vec<String>,
+    selected: Option<usize>,
+}
+
+impl Widget for WindowsDropdown {
+    fn render(&self) -> String {
+        STYLE.dropdown(self.selected())
+    }
+}
+
+impl Dropdown for WindowsDropdown {
+    fn select(&mut self, option: &str) -> bool {
+        let Some(index) = self.options.iter().position(|candidate| candidate == option) else {
+            return false;
+        };
+
+        self.selected = Some(index);
+        println!("Fluent dropdown picked {}", option);
+
+        true
+    }
+
+    fn selected(&self) -> Option<&str> {
+        self.selected.map(|index| self.options[index].as_str())
+    }
+}
+
+struct WindowsLabel {
+    text: String,
+}
+
+impl Widget for WindowsLabel {
+    fn render(&self) -> String {
+        self.text.clone()
+    }
+}
+
+impl Label for WindowsLabel {
+    fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn show(&self) {
+        println!("Fluent label: {}", self.text);
+    }
+}
+
+struct WindowsSlider {
+    min: i32,
+    max: i32,
+    value: i32,
+}
+
+impl Widget for WindowsSlider {
+    fn render(&self) -> String {
+        STYLE.slider(self.min, self.max, self.value)
+    }
+}
+
+impl Slider for WindowsSlider {
+    fn slide(&mut self, value: i32) {
+        self.value = value.clamp(self.min, self.max);
+        println!("Fluent slider moved to {}", self.value);
+    }
+
+    fn value(&self) -> i32 {
+        self.value
+    }
+}
+
+struct WindowsDialog {
+    title: String,
+}
+
+impl Widget for WindowsDialog {
+    fn render(&self) -> String {
+        STYLE.dialog(&self.title)
+    }
+}
+
+impl Dialog for WindowsDialog {
+    fn open(&self) {
+        println!("Fluent dialog {} opens", self.title);
+    }
+
+    fn close(&self) {
+        println!("Fluent dialog {} closes", self.title);
+    }
+}
+
+pub struct WindowsUIManager {}
+impl UIManager for WindowsUIManager {
+    fn style(&self) -> &'static Style {
+        &STYLE
+    }
+
+    fn create_button(&self, label: &str) -> Box<dyn Button> {
+        Box::new(WindowsButton{ label: label.to_string(), listeners: Listeners::default() })
+    }
+
+    fn create_input(&self) -> Box<dyn Input> {
+        Box::new(WindowsInput{ state: InputState::default() })
+    }
+
+    fn create_checkbox(&self, label: &str) -> Box<dyn Checkbox> {
+        Box::new(WindowsCheckbox{ label: label.to_string(), checked: false })
+    }
+
+    fn create_dropdown(&self, options: &[&str]) -> Box<dyn Dropdown> {
+        Box::new(WindowsDropdown{ options: options.iter().map(|option| option.to_string()).collect(), selected: None })
+    }
+
+    fn create_label(&self, text: &str) -> Box<dyn Label> {
+        Box::new(WindowsLabel{ text: text.to_string() })
+    }
+
+    fn create_slider(&self, min: i32, max: i32) -> Box<dyn Slider> {
+        let (min, max) = (min.min(max), min.max(max));
+
+        Box::new(WindowsSlider{ min, max, value: min })
+    }
+
+    fn create_dialog(&self, title: &str) -> Box<dyn Dialog> {
+        Box::new(WindowsDialog{ title: title.to_string() })
+    }
+}
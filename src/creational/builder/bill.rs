@@ -0,0 +1,57 @@
+// A bill of materials lists every part of the build along with its price,
+// so you know where the money went.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Part, PartKind, PartSelection};
+
+pub fn format_price(cents: u32) -> String {
+    format!("${}.{:02}", cents / 100, cents % 100)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BillItem {
+    pub kind: PartKind,
+    pub name: String,
+    pub price: u32,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BillOfMaterials {
+    pub items: Vec<BillItem>,
+    pub total: u32,
+}
+
+impl BillOfMaterials {
+    pub fn from_parts(parts: &PartSelection) -> BillOfMaterials {
+        let mut bill = BillOfMaterials::default();
+
+        bill.add(parts.processor.iter());
+        bill.add(parts.motherboard.iter());
+        bill.add(parts.graphic_cards.iter());
+        bill.add(parts.memory.iter());
+        bill.add(parts.storage.iter());
+        bill.add(parts.power_supply.iter());
+
+        bill
+    }
+
+    fn add<'a, T: Part + 'a>(&mut self, parts: impl Iterator<Item = &'a T>) {
+        for part in parts {
+            self.items.push(BillItem { kind: part.kind(), name: part.name().to_string(), price: part.price() });
+            self.total += part.price();
+        }
+    }
+}
+
+impl fmt::Display for BillOfMaterials {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for item in self.items.iter() {
+            writeln!(f, "{:<14} {:<24} {:>12}", item.kind.to_string(), item.name, format_price(item.price))?;
+        }
+
+        write!(f, "{:<39} {:>12}", "Total", format_price(self.total))
+    }
+}
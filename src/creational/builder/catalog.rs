@@ -0,0 +1,122 @@
+// PartCatalog is the shop shelf. It's loaded from a JSON file that looks like
+//
+// { "processors": [...], "motherboards": [...], "graphic_cards": [...],
+//   "memory": [...], "storage": [...], "power_supplies": [...] }
+//
+// Missing lists are treated as empty. The `compatible_*` methods use the same rules and
+// budget as `PCBuilder::buy_*`, so anything they return can be bought without being rejected.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{GraphicCard, Memory, Motherboard, PCBuilder, Part, PowerSupply, Processor, Storage};
+
+#[derive(Debug)]
+pub enum CatalogError {
+    Io(io::Error),
+    Parse(serde_json::Error),
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CatalogError::Io(err) => write!(f, "Failed to read the catalog: {}", err),
+            CatalogError::Parse(err) => write!(f, "Failed to parse the catalog: {}", err),
+        }
+    }
+}
+
+impl Error for CatalogError {}
+
+impl From<io::Error> for CatalogError {
+    fn from(err: io::Error) -> Self {
+        CatalogError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for CatalogError {
+    fn from(err: serde_json::Error) -> Self {
+        CatalogError::Parse(err)
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PartCatalog {
+    pub processors: Vec<Processor>,
+    pub motherboards: Vec<Motherboard>,
+    pub graphic_cards: Vec<GraphicCard>,
+    pub memory: Vec<Memory>,
+    pub storage: Vec<Storage>,
+    pub power_supplies: Vec<PowerSupply>,
+}
+
+fn compatible<'a, T: Part>(parts: &'a [T], builder: &PCBuilder) -> Vec<&'a T> {
+    parts.iter().filter(|part| builder.fits(*part)).collect()
+}
+
+#[allow(dead_code)]
+impl PartCatalog {
+    pub fn from_json(json: &str) -> Result<PartCatalog, CatalogError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<PartCatalog, CatalogError> {
+        let json = fs::read_to_string(path)?;
+
+        PartCatalog::from_json(&json)
+    }
+
+    pub fn compatible_processors(&self, builder: &PCBuilder) -> Vec<&Processor> {
+        compatible(&self.processors, builder)
+    }
+
+    pub fn compatible_motherboards(&self, builder: &PCBuilder) -> Vec<&Motherboard> {
+        compatible(&self.motherboards, builder)
+    }
+
+    pub fn compatible_graphic_cards(&self, builder: &PCBuilder) -> Vec<&GraphicCard> {
+        compatible(&self.graphic_cards, builder)
+    }
+
+    pub fn compatible_memory(&self, builder: &PCBuilder) -> Vec<&Memory> {
+        compatible(&self.memory, builder)
+    }
+
+    pub fn compatible_storage(&self, builder: &PCBuilder) -> Vec<&Storage> {
+        compatible(&self.storage, builder)
+    }
+
+    pub fn compatible_power_supplies(&self, builder: &PCBuilder) -> Vec<&PowerSupply> {
+        compatible(&self.power_supplies, builder)
+    }
+
+    pub fn find_processor(&self, series: &str) -> Option<&Processor> {
+        self.processors.iter().find(|processor| processor.series == series)
+    }
+
+    pub fn find_motherboard(&self, model: &str) -> Option<&Motherboard> {
+        self.motherboards.iter().find(|motherboard| motherboard.model == model)
+    }
+
+    pub fn find_graphic_card(&self, model: &str) -> Option<&GraphicCard> {
+        self.graphic_cards.iter().find(|vga| vga.model == model)
+    }
+
+    pub fn find_memory(&self, model: &str) -> Option<&Memory> {
+        self.memory.iter().find(|ram| ram.model == model)
+    }
+
+    pub fn find_storage(&self, model: &str) -> Option<&Storage> {
+        self.storage.iter().find(|storage| storage.model == model)
+    }
+
+    pub fn find_power_supply(&self, model: &str) -> Option<&PowerSupply> {
+        self.power_supplies.iter().find(|psu| psu.model == model)
+    }
+}
@@ -0,0 +1,50 @@
+use std::error::Error;
+use std::fmt;
+
+use super::bill::format_price;
+use super::{PartKind, PowerBudget, RuleViolation};
+
+// BuildError tells the caller exactly why a part or a build was rejected,
+// so they can react to it instead of reading the console
+#[derive(Debug)]
+pub enum BuildError {
+    MissingParts(Vec<PartKind>),
+    Incompatible(Vec<RuleViolation>),
+    InsufficientPower(PowerBudget),
+    OverBudget { budget: u32, total: u32 },
+    NoSuitableBuild,
+    NoSuchPart { kind: PartKind, index: usize },
+    InvalidSession(serde_json::Error),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::MissingParts(kinds) => {
+                let names: Vec<String> = kinds.iter().map(|kind| kind.to_string()).collect();
+
+                write!(f, "This PC is still missing the following parts: {}", names.join(", "))
+            },
+            BuildError::Incompatible(violations) => {
+                let reasons: Vec<String> = violations.iter().map(|violation| violation.to_string()).collect();
+
+                write!(f, "Incompatible parts: {}", reasons.join("; "))
+            },
+            BuildError::InsufficientPower(budget) => write!(
+                f,
+                "The power supply only provides {}W while the PC draws {}W at full load",
+                budget.capacity, budget.draw(),
+            ),
+            BuildError::OverBudget { budget, total } => write!(
+                f,
+                "The parts would cost {} which is over the budget of {}",
+                format_price(*total), format_price(*budget),
+            ),
+            BuildError::NoSuitableBuild => write!(f, "No combination of parts satisfies the constraints"),
+            BuildError::NoSuchPart { kind, index } => write!(f, "There is no {} at position {}", kind, index),
+            BuildError::InvalidSession(err) => write!(f, "Failed to save or restore the build: {}", err),
+        }
+    }
+}
+
+impl Error for BuildError {}
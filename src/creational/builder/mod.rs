@@ -0,0 +1,578 @@
+// Builder pattern lets you construct a complex object step by step
+//
+// In this case, let's say that you want to build a PC. As a first time
+// however, you have difficulties in choosing the best part for your dream
+// rig. Moreover, it seems like a new-and-better product will be coming soon.
+// So, you buy those parts step-by-step and build them when all required
+// parts are present.
+//
+// A PC may have several graphic cards, memory sticks and drives, as long as the motherboard
+// has enough slots for them. Graphic cards can be left out entirely.
+//
+// Moreover, a builder prevents you from making rookie mistakes such as choosing the wrong
+// socket of CPU and motherboard or choosing outdated memory. Those checks are written
+// as compatibility rules (see `rules.rs`) that are evaluated every time you buy a part.
+// Before the PC is built, the power supply is checked against the power budget (see `power.rs`).
+//
+// `PCBuilder` only finds out about missing parts at runtime. If you know the parts
+// upfront, `TypedPCBuilder` (see `typestate.rs`) turns a missing part into a compile error.
+//
+// Parts don't have to be written by hand either, `PartCatalog` (see `catalog.rs`) loads them
+// from a JSON file and tells you which ones fit the parts you already have.
+//
+// Every part has a price. The builder keeps a running total, can refuse parts that would
+// go over your budget, and hands you a bill of materials (see `bill.rs`) with the PC.
+//
+// If you'd rather not pick the parts at all, `suggest_build` (see `solver.rs`) searches
+// the catalog for the best build that satisfies your constraints.
+//
+// Since buying parts takes a while, an unfinished build can be saved as JSON and resumed
+// later. The restored parts are checked again against the rules and the budget, and whatever
+// no longer fits is handed back with the builder so it can be fixed.
+
+mod bill;
+mod catalog;
+mod error;
+mod power;
+mod rules;
+mod solver;
+mod typestate;
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[allow(unused_imports)]
+pub use bill::{BillItem, BillOfMaterials};
+#[allow(unused_imports)]
+pub use catalog::{CatalogError, PartCatalog};
+pub use error::BuildError;
+pub use power::{PowerBudget, PowerConfig};
+#[allow(unused_imports)]
+pub use rules::{CompatibilityRule, PartRef, RuleViolation};
+pub use solver::{suggest_build, BuildConstraints};
+#[allow(unused_imports)]
+pub use typestate::{Missing, Supplied, TypedPCBuilder};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PartKind {
+    Processor,
+    Motherboard,
+    GraphicCard,
+    Memory,
+    Storage,
+    PowerSupply,
+}
+
+impl fmt::Display for PartKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            PartKind::Processor => "processor",
+            PartKind::Motherboard => "motherboard",
+            PartKind::GraphicCard => "graphic card",
+            PartKind::Memory => "memory",
+            PartKind::Storage => "storage",
+            PartKind::PowerSupply => "power supply",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Processor {
+    pub socket: String,
+    pub series: String,
+    pub cores: u8,
+    pub threads: u16,
+    pub tdp: u16,
+    // Prices are in cents to avoid rounding errors
+    pub price: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Motherboard {
+    pub model: String,
+    pub socket: String,
+    pub memory_type: String,
+    pub storage_type: Vec<String>,
+    pub pcie: u8,
+    pub max_memory_per_slot: u32,
+    pub memory_slots: u8,
+    pub storage_slots: u8,
+    pub pcie_slots: u8,
+    pub price: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[allow(dead_code)]
+pub struct GraphicCard {
+    pub model: String,
+    pub socket: String,
+    pub pcie: u8,
+    pub memory: u32,
+    pub tdp: u16,
+    pub price: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Memory {
+    pub model: String,
+    pub socket: String,
+    pub capacity: u32,
+    pub price: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Storage {
+    pub model: String,
+    pub socket: String,
+    pub price: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PowerSupply {
+    pub model: String,
+    pub capacity: u32,
+    pub price: u32,
+}
+
+// Part lets the builder and the catalog treat every kind of part the same way
+pub trait Part: Clone {
+    fn kind(&self) -> PartKind;
+    fn name(&self) -> &str;
+    fn price(&self) -> u32;
+
+    // Puts the part into its slot. Single-slot parts replace the previous one,
+    // the rest are added next to the ones that are already there.
+    fn place(self, parts: &mut PartSelection);
+}
+
+impl Part for Processor {
+    fn kind(&self) -> PartKind {
+        PartKind::Processor
+    }
+
+    fn name(&self) -> &str {
+        &self.series
+    }
+
+    fn price(&self) -> u32 {
+        self.price
+    }
+
+    fn place(self, parts: &mut PartSelection) {
+        parts.processor = Some(self);
+    }
+}
+
+impl Part for Motherboard {
+    fn kind(&self) -> PartKind {
+        PartKind::Motherboard
+    }
+
+    fn name(&self) -> &str {
+        &self.model
+    }
+
+    fn price(&self) -> u32 {
+        self.price
+    }
+
+    fn place(self, parts: &mut PartSelection) {
+        parts.motherboard = Some(self);
+    }
+}
+
+impl Part for GraphicCard {
+    fn kind(&self) -> PartKind {
+        PartKind::GraphicCard
+    }
+
+    fn name(&self) -> &str {
+        &self.model
+    }
+
+    fn price(&self) -> u32 {
+        self.price
+    }
+
+    fn place(self, parts: &mut PartSelection) {
+        parts.graphic_cards.push(self);
+    }
+}
+
+impl Part for Memory {
+    fn kind(&self) -> PartKind {
+        PartKind::Memory
+    }
+
+    fn name(&self) -> &str {
+        &self.model
+    }
+
+    fn price(&self) -> u32 {
+        self.price
+    }
+
+    fn place(self, parts: &mut PartSelection) {
+        parts.memory.push(self);
+    }
+}
+
+impl Part for Storage {
+    fn kind(&self) -> PartKind {
+        PartKind::Storage
+    }
+
+    fn name(&self) -> &str {
+        &self.model
+    }
+
+    fn price(&self) -> u32 {
+        self.price
+    }
+
+    fn place(self, parts: &mut PartSelection) {
+        parts.storage.push(self);
+    }
+}
+
+impl Part for PowerSupply {
+    fn kind(&self) -> PartKind {
+        PartKind::PowerSupply
+    }
+
+    fn name(&self) -> &str {
+        &self.model
+    }
+
+    fn price(&self) -> u32 {
+        self.price
+    }
+
+    fn place(self, parts: &mut PartSelection) {
+        parts.power_supply = Some(self);
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[allow(dead_code)]
+pub struct PC {
+    pub processor: Processor,
+    pub motherboard: Motherboard,
+    pub graphic_cards: Vec<GraphicCard>,
+    pub memory: Vec<Memory>,
+    pub storage: Vec<Storage>,
+    pub power_supply: PowerSupply,
+    // A build without enough headroom is still built, callers can check `has_headroom`
+    pub power_budget: PowerBudget,
+    pub bill_of_materials: BillOfMaterials,
+}
+
+// PartSelection holds the parts that have been bought so far. Compatibility rules
+// are evaluated against it, so a candidate part can be checked without touching the builder.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PartSelection {
+    pub processor: Option<Processor>,
+    pub motherboard: Option<Motherboard>,
+    pub graphic_cards: Vec<GraphicCard>,
+    pub memory: Vec<Memory>,
+    pub storage: Vec<Storage>,
+    pub power_supply: Option<PowerSupply>,
+}
+
+impl PartSelection {
+    // Graphic cards are optional, some processors come with integrated graphics
+    pub fn missing_parts(&self) -> Vec<PartKind> {
+        let mut missing = vec![];
+
+        if self.processor.is_none() {
+            missing.push(PartKind::Processor);
+        }
+        if self.motherboard.is_none() {
+            missing.push(PartKind::Motherboard);
+        }
+        if self.memory.is_empty() {
+            missing.push(PartKind::Memory);
+        }
+        if self.storage.is_empty() {
+            missing.push(PartKind::Storage);
+        }
+        if self.power_supply.is_none() {
+            missing.push(PartKind::PowerSupply);
+        }
+
+        missing
+    }
+
+    pub fn total_price(&self) -> u32 {
+        BillOfMaterials::from_parts(self).total
+    }
+
+    pub fn total_memory(&self) -> u32 {
+        self.memory.iter().map(|ram| ram.capacity).sum()
+    }
+}
+
+// Rules are behavior instead of data, a resumed builder gets the default rules back
+#[derive(Serialize, Deserialize)]
+pub struct PCBuilder {
+    parts: PartSelection,
+    #[serde(skip, default = "rules::default_rules")]
+    rules: Vec<Box<dyn CompatibilityRule>>,
+    power_config: PowerConfig,
+    budget: Option<u32>,
+}
+
+#[allow(dead_code)]
+impl PCBuilder {
+    pub fn new_builder() -> PCBuilder {
+        PCBuilder { parts: PartSelection::default(), rules: rules::default_rules(), power_config: PowerConfig::default(), budget: None }
+    }
+
+    pub fn set_power_config(&mut self, config: PowerConfig) -> &PCBuilder {
+        self.power_config = config;
+
+        self
+    }
+
+    pub fn set_power_headroom(&mut self, headroom_percent: u32) -> &PCBuilder {
+        self.power_config.headroom_percent = headroom_percent;
+
+        self
+    }
+
+    // Caps the total price of the parts, `None` removes the cap. Parts that were bought before
+    // are kept even when they cost more, but the PC can't be built until some are sold.
+    pub fn set_budget(&mut self, budget: Option<u32>) -> &PCBuilder {
+        self.budget = budget;
+
+        self
+    }
+
+    pub fn total_price(&self) -> u32 {
+        self.parts.total_price()
+    }
+
+    // Total capacity of all memory sticks in GB
+    pub fn total_memory(&self) -> u32 {
+        self.parts.total_memory()
+    }
+
+    pub fn power_budget(&self) -> PowerBudget {
+        PowerBudget::compute(&self.parts, &self.power_config)
+    }
+
+    pub fn add_rule(&mut self, rule: Box<dyn CompatibilityRule>) -> &PCBuilder {
+        self.rules.push(rule);
+
+        self
+    }
+
+    pub fn parts(&self) -> &PartSelection {
+        &self.parts
+    }
+
+    // Evaluates all rules against the current selection
+    pub fn check_compatibility(&self) -> Vec<RuleViolation> {
+        self.rules.iter().filter_map(|rule| rule.check(&self.parts)).collect()
+    }
+
+    pub fn to_json(&self) -> Result<String, BuildError> {
+        serde_json::to_string(self).map_err(BuildError::InvalidSession)
+    }
+
+    // Restores a saved build along with whatever is wrong with it now, since the rules
+    // might have changed and the parts might no longer fit the saved budget. Only a
+    // session that can't be read at all is an error. Custom rules have to be added
+    // again, call `check_compatibility` after adding them to validate the restored parts.
+    pub fn from_json(json: &str) -> Result<(PCBuilder, Vec<RuleViolation>), BuildError> {
+        let builder: PCBuilder = serde_json::from_str(json).map_err(BuildError::InvalidSession)?;
+
+        let mut violations = builder.check_compatibility();
+        if let Err(err) = builder.check_budget(&builder.parts) {
+            violations.push(RuleViolation { rule: "budget", parts: vec![], reason: err.to_string() });
+        }
+
+        Ok((builder, violations))
+    }
+
+    // Only violations caused by the new part should prevent the purchase, so
+    // a rule that is added later doesn't block every other purchase
+    fn violations_for(&self, candidate: &PartSelection, kind: PartKind) -> Vec<RuleViolation> {
+        self.rules
+            .iter()
+            .filter_map(|rule| rule.check(candidate))
+            .filter(|violation| violation.involves(kind))
+            .collect()
+    }
+
+    // Runs the same checks as a purchase, the rules first and then the budget
+    fn check_purchase(&self, candidate: &PartSelection, kind: PartKind) -> Result<(), BuildError> {
+        let violations = self.violations_for(candidate, kind);
+        if !violations.is_empty() {
+            return Err(BuildError::Incompatible(violations));
+        }
+
+        self.check_budget(candidate)
+    }
+
+    fn check_budget(&self, parts: &PartSelection) -> Result<(), BuildError> {
+        if let Some(budget) = self.budget {
+            let total = parts.total_price();
+            if total > budget {
+                return Err(BuildError::OverBudget { budget, total });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Checks whether a part can be bought without actually buying it
+    pub fn fits<T: Part>(&self, part: &T) -> bool {
+        let kind = part.kind();
+        let mut candidate = self.parts.clone();
+        part.clone().place(&mut candidate);
+
+        self.check_purchase(&candidate, kind).is_ok()
+    }
+
+    pub fn buy<T: Part>(&mut self, part: T) -> Result<&mut PCBuilder, BuildError> {
+        let kind = part.kind();
+        let mut candidate = self.parts.clone();
+        part.place(&mut candidate);
+
+        self.check_purchase(&candidate, kind)?;
+        self.parts = candidate;
+
+        Ok(self)
+    }
+
+    pub fn buy_processor(&mut self, processor: Processor) -> Result<&mut PCBuilder, BuildError> {
+        self.buy(processor)
+    }
+
+    pub fn sell_processor(&mut self) -> &PCBuilder {
+        self.parts.processor = None;
+
+        self
+    }
+
+    pub fn buy_motherboard(&mut self, motherboard: Motherboard) -> Result<&mut PCBuilder, BuildError> {
+        self.buy(motherboard)
+    }
+
+    pub fn sell_motherboard(&mut self) -> &PCBuilder {
+        self.parts.motherboard = None;
+
+        self
+    }
+
+    pub fn buy_graphic_card(&mut self, vga: GraphicCard) -> Result<&mut PCBuilder, BuildError> {
+        self.buy(vga)
+    }
+
+    // Sells the graphic card at the given position of `parts().graphic_cards`
+    pub fn sell_graphic_card(&mut self, index: usize) -> Result<&mut PCBuilder, BuildError> {
+        if index >= self.parts.graphic_cards.len() {
+            return Err(BuildError::NoSuchPart { kind: PartKind::GraphicCard, index });
+        }
+
+        self.parts.graphic_cards.remove(index);
+
+        Ok(self)
+    }
+
+    pub fn buy_memory(&mut self, ram: Memory) -> Result<&mut PCBuilder, BuildError> {
+        self.buy(ram)
+    }
+
+    pub fn sell_memory(&mut self, index: usize) -> Result<&mut PCBuilder, BuildError> {
+        if index >= self.parts.memory.len() {
+            return Err(BuildError::NoSuchPart { kind: PartKind::Memory, index });
+        }
+
+        self.parts.memory.remove(index);
+
+        Ok(self)
+    }
+
+    pub fn buy_storage(&mut self, storage: Storage) -> Result<&mut PCBuilder, BuildError> {
+        self.buy(storage)
+    }
+
+    pub fn sell_storage(&mut self, index: usize) -> Result<&mut PCBuilder, BuildError> {
+        if index >= self.parts.storage.len() {
+            return Err(BuildError::NoSuchPart { kind: PartKind::Storage, index });
+        }
+
+        self.parts.storage.remove(index);
+
+        Ok(self)
+    }
+
+    pub fn buy_power_supply(&mut self, psu: PowerSupply) -> Result<&mut PCBuilder, BuildError> {
+        self.buy(psu)
+    }
+
+    pub fn sell_power_supply(&mut self) -> &PCBuilder {
+        self.parts.power_supply = None;
+
+        self
+    }
+
+    pub fn sell_all_parts(&mut self) -> &PCBuilder {
+        self.parts = PartSelection::default();
+
+        self
+    }
+
+    pub fn build_pc(&self) -> Result<PC, BuildError> {
+        let missing = self.parts.missing_parts();
+        if !missing.is_empty() {
+            return Err(BuildError::MissingParts(missing));
+        }
+
+        self.assemble()
+    }
+
+    // Checks the rules, the budget and the power supply of the current parts
+    fn validate(&self) -> Result<PowerBudget, BuildError> {
+        // rules might have been added after the parts were bought
+        let violations = self.check_compatibility();
+        if !violations.is_empty() {
+            return Err(BuildError::Incompatible(violations));
+        }
+
+        // and the budget might have been lowered since
+        self.check_budget(&self.parts)?;
+
+        let power_budget = self.power_budget();
+        if !power_budget.is_sufficient() {
+            return Err(BuildError::InsufficientPower(power_budget));
+        }
+
+        Ok(power_budget)
+    }
+
+    // Validates the build and puts the parts together. Callers must make sure that
+    // no required part is missing (see `missing_parts`).
+    fn assemble(&self) -> Result<PC, BuildError> {
+        let parts = &self.parts;
+        let power_budget = self.validate()?;
+
+        let new_pc = PC{
+            processor: parts.processor.as_ref().unwrap().to_owned(),
+            motherboard: parts.motherboard.as_ref().unwrap().to_owned(),
+            graphic_cards: parts.graphic_cards.clone(),
+            memory: parts.memory.clone(),
+            storage: parts.storage.clone(),
+            power_supply: parts.power_supply.as_ref().unwrap().to_owned(),
+            power_budget,
+            bill_of_materials: BillOfMaterials::from_parts(parts),
+        };
+
+        Ok(new_pc)
+    }
+}
@@ -0,0 +1,69 @@
+// A PC that draws more power than its power supply provides won't boot.
+//
+// Processors and graphic cards come with their own TDP, the rest of the parts
+// are estimated per stick or drive since their draw is rarely printed on the box. On top of that,
+// a healthy build keeps some headroom so the power supply isn't running at its limit.
+
+use serde::{Deserialize, Serialize};
+
+use super::PartSelection;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PowerConfig {
+    pub motherboard_watts: u32,
+    pub memory_watts: u32,
+    pub storage_watts: u32,
+    pub headroom_percent: u32,
+}
+
+impl Default for PowerConfig {
+    fn default() -> Self {
+        PowerConfig { motherboard_watts: 50, memory_watts: 5, storage_watts: 10, headroom_percent: 20 }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PowerBudget {
+    pub processor: u32,
+    pub graphic_card: u32,
+    pub motherboard: u32,
+    pub memory: u32,
+    pub storage: u32,
+    pub headroom_percent: u32,
+    pub capacity: u32,
+}
+
+impl PowerBudget {
+    pub fn compute(parts: &PartSelection, config: &PowerConfig) -> PowerBudget {
+        PowerBudget {
+            processor: parts.processor.as_ref().map_or(0, |processor| processor.tdp as u32),
+            graphic_card: parts.graphic_cards.iter().map(|vga| vga.tdp as u32).sum(),
+            motherboard: parts.motherboard.as_ref().map_or(0, |_| config.motherboard_watts),
+            memory: parts.memory.len() as u32 * config.memory_watts,
+            storage: parts.storage.len() as u32 * config.storage_watts,
+            headroom_percent: config.headroom_percent,
+            capacity: parts.power_supply.as_ref().map_or(0, |psu| psu.capacity),
+        }
+    }
+
+    // The total draw of all parts when they are running at full load
+    pub fn draw(&self) -> u32 {
+        self.processor + self.graphic_card + self.motherboard + self.memory + self.storage
+    }
+
+    // The power supply capacity we would like to have, including headroom. The math is done in
+    // u64 so a large headroom can't overflow, a result that doesn't fit is capped at u32::MAX.
+    pub fn recommended(&self) -> u32 {
+        let recommended = self.draw() as u64 * (100 + self.headroom_percent as u64) / 100;
+
+        u32::try_from(recommended).unwrap_or(u32::MAX)
+    }
+
+    pub fn is_sufficient(&self) -> bool {
+        self.capacity >= self.draw()
+    }
+
+    pub fn has_headroom(&self) -> bool {
+        self.capacity >= self.recommended()
+    }
+}
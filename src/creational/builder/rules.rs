@@ -0,0 +1,273 @@
+// Compatibility rules decide whether a set of parts can live together in one PC.
+//
+// Instead of hard-coding socket comparisons in every `buy_*` method, each rule
+// looks at the whole (possibly partial) selection and reports a violation when
+// the parts it cares about don't fit. New rules, such as form factor or GPU length
+// checks, can be plugged into the builder without touching the `buy_*` methods.
+
+use std::fmt;
+
+use super::{Motherboard, Part, PartKind, PartSelection};
+
+// PartRef points at one bought part. `index` is its position among the parts of the same
+// kind, so it can be passed to `sell_memory` and friends.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRef {
+    pub kind: PartKind,
+    pub index: usize,
+    pub model: String,
+}
+
+impl PartRef {
+    pub fn new<T: Part>(part: &T, index: usize) -> PartRef {
+        PartRef { kind: part.kind(), index, model: part.name().to_string() }
+    }
+}
+
+impl fmt::Display for PartRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} #{} ({})", self.kind, self.index, self.model)
+    }
+}
+
+// RuleViolation describes which rule failed and lists every part that is in conflict
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuleViolation {
+    pub rule: &'static str,
+    pub parts: Vec<PartRef>,
+    pub reason: String,
+}
+
+impl RuleViolation {
+    pub fn involves(&self, kind: PartKind) -> bool {
+        self.parts.iter().any(|part| part.kind == kind)
+    }
+}
+
+impl fmt::Display for RuleViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] {}", self.rule, self.reason)
+    }
+}
+
+// Every part of a kind that matches the predicate, with its position
+fn offending<T: Part>(parts: &[T], predicate: impl Fn(&T) -> bool) -> Vec<(PartRef, &T)> {
+    parts
+        .iter()
+        .enumerate()
+        .filter(|(_, part)| predicate(part))
+        .map(|(index, part)| (PartRef::new(part, index), part))
+        .collect()
+}
+
+// Puts the offending parts in front of the motherboard they conflict with
+fn with_motherboard(offending: Vec<PartRef>, motherboard: &Motherboard) -> Vec<PartRef> {
+    let mut parts = offending;
+    parts.push(PartRef::new(motherboard, 0));
+
+    parts
+}
+
+pub trait CompatibilityRule {
+    fn name(&self) -> &'static str;
+
+    // Rules should only report a violation when all parts they inspect are present,
+    // a partial build is not incompatible just because it's incomplete
+    fn check(&self, parts: &PartSelection) -> Option<RuleViolation>;
+}
+
+// The rules every PC must satisfy
+pub fn default_rules() -> Vec<Box<dyn CompatibilityRule>> {
+    vec![
+        Box::new(ProcessorSocketRule),
+        Box::new(MemoryTypeRule),
+        Box::new(StorageInterfaceRule),
+        Box::new(PCIeGenerationRule),
+        Box::new(MemoryCapacityRule),
+        Box::new(SlotCountRule),
+    ]
+}
+
+pub struct ProcessorSocketRule;
+impl CompatibilityRule for ProcessorSocketRule {
+    fn name(&self) -> &'static str {
+        "processor-socket"
+    }
+
+    fn check(&self, parts: &PartSelection) -> Option<RuleViolation> {
+        let processor = parts.processor.as_ref()?;
+        let motherboard = parts.motherboard.as_ref()?;
+
+        if processor.socket == motherboard.socket {
+            return None;
+        }
+
+        Some(RuleViolation {
+            rule: self.name(),
+            parts: vec![PartRef::new(processor, 0), PartRef::new(motherboard, 0)],
+            reason: format!(
+                "The processor requires {} socket but the motherboard only supports {} processors",
+                processor.socket, motherboard.socket,
+            ),
+        })
+    }
+}
+
+pub struct MemoryTypeRule;
+impl CompatibilityRule for MemoryTypeRule {
+    fn name(&self) -> &'static str {
+        "memory-type"
+    }
+
+    fn check(&self, parts: &PartSelection) -> Option<RuleViolation> {
+        let motherboard = parts.motherboard.as_ref()?;
+        let memory = offending(&parts.memory, |ram| ram.socket != motherboard.memory_type);
+        if memory.is_empty() {
+            return None;
+        }
+
+        let reasons: Vec<String> = memory
+            .iter()
+            .map(|(_, ram)| format!("{} is a {} RAM", ram.model, ram.socket))
+            .collect();
+
+        Some(RuleViolation {
+            rule: self.name(),
+            parts: with_motherboard(memory.into_iter().map(|(part, _)| part).collect(), motherboard),
+            reason: format!("{} but the motherboard only supports {} RAM", reasons.join(", "), motherboard.memory_type),
+        })
+    }
+}
+
+pub struct StorageInterfaceRule;
+impl CompatibilityRule for StorageInterfaceRule {
+    fn name(&self) -> &'static str {
+        "storage-interface"
+    }
+
+    fn check(&self, parts: &PartSelection) -> Option<RuleViolation> {
+        let motherboard = parts.motherboard.as_ref()?;
+        let storage = offending(&parts.storage, |storage| !motherboard.storage_type.contains(&storage.socket));
+        if storage.is_empty() {
+            return None;
+        }
+
+        let reasons: Vec<String> = storage
+            .iter()
+            .map(|(_, storage)| format!("{} requires {} interface", storage.model, storage.socket))
+            .collect();
+
+        Some(RuleViolation {
+            rule: self.name(),
+            parts: with_motherboard(storage.into_iter().map(|(part, _)| part).collect(), motherboard),
+            reason: format!("{} which the motherboard does not support", reasons.join(", ")),
+        })
+    }
+}
+
+// PCIe is backward compatible in real life, but a card running on an older
+// generation slot is usually not what the buyer wants
+pub struct PCIeGenerationRule;
+impl CompatibilityRule for PCIeGenerationRule {
+    fn name(&self) -> &'static str {
+        "pcie-generation"
+    }
+
+    fn check(&self, parts: &PartSelection) -> Option<RuleViolation> {
+        let motherboard = parts.motherboard.as_ref()?;
+        let graphic_cards = offending(&parts.graphic_cards, |vga| vga.pcie > motherboard.pcie);
+        if graphic_cards.is_empty() {
+            return None;
+        }
+
+        let reasons: Vec<String> = graphic_cards
+            .iter()
+            .map(|(_, vga)| format!("{} requires PCIe {}", vga.model, vga.pcie))
+            .collect();
+
+        Some(RuleViolation {
+            rule: self.name(),
+            parts: with_motherboard(graphic_cards.into_iter().map(|(part, _)| part).collect(), motherboard),
+            reason: format!("{} but the motherboard only provides PCIe {}", reasons.join(", "), motherboard.pcie),
+        })
+    }
+}
+
+pub struct MemoryCapacityRule;
+impl CompatibilityRule for MemoryCapacityRule {
+    fn name(&self) -> &'static str {
+        "memory-capacity"
+    }
+
+    fn check(&self, parts: &PartSelection) -> Option<RuleViolation> {
+        let motherboard = parts.motherboard.as_ref()?;
+        let memory = offending(&parts.memory, |ram| ram.capacity > motherboard.max_memory_per_slot);
+        if memory.is_empty() {
+            return None;
+        }
+
+        let reasons: Vec<String> = memory
+            .iter()
+            .map(|(_, ram)| format!("{} has {} GB per stick", ram.model, ram.capacity))
+            .collect();
+
+        Some(RuleViolation {
+            rule: self.name(),
+            parts: with_motherboard(memory.into_iter().map(|(part, _)| part).collect(), motherboard),
+            reason: format!(
+                "{} but the motherboard only supports {} GB per slot",
+                reasons.join(", "), motherboard.max_memory_per_slot,
+            ),
+        })
+    }
+}
+
+// A motherboard can only hold as many sticks, drives and cards as it has slots
+pub struct SlotCountRule;
+impl CompatibilityRule for SlotCountRule {
+    fn name(&self) -> &'static str {
+        "slot-count"
+    }
+
+    fn check(&self, parts: &PartSelection) -> Option<RuleViolation> {
+        let motherboard = parts.motherboard.as_ref()?;
+        let slots = [
+            (PartKind::Memory, excess(&parts.memory, motherboard.memory_slots)),
+            (PartKind::Storage, excess(&parts.storage, motherboard.storage_slots)),
+            (PartKind::GraphicCard, excess(&parts.graphic_cards, motherboard.pcie_slots)),
+        ];
+
+        let full: Vec<(PartKind, Vec<PartRef>)> = slots
+            .into_iter()
+            .filter(|(_, excess)| !excess.is_empty())
+            .collect();
+        if full.is_empty() {
+            return None;
+        }
+
+        let reasons: Vec<String> = full
+            .iter()
+            .map(|(kind, excess)| {
+                let models: Vec<&str> = excess.iter().map(|part| part.model.as_str()).collect();
+
+                format!("no {} slot is left for {}", kind, models.join(", "))
+            })
+            .collect();
+
+        Some(RuleViolation {
+            rule: self.name(),
+            parts: with_motherboard(full.into_iter().flat_map(|(_, excess)| excess).collect(), motherboard),
+            reason: format!("The motherboard is full, {}", reasons.join("; ")),
+        })
+    }
+}
+
+// The parts that don't fit in the slots, the ones bought last are left out first
+fn excess<T: Part>(parts: &[T], slots: u8) -> Vec<PartRef> {
+    parts
+        .iter()
+        .enumerate()
+        .skip(slots as usize)
+        .map(|(index, part)| PartRef::new(part, index))
+        .collect()
+}
@@ -0,0 +1,135 @@
+// The solver picks the parts for you. It walks through every combination in the catalog
+// by buying and selling parts on a regular `PCBuilder`, so a suggestion goes through the
+// exact same compatibility, budget and power checks as a hand-picked build.
+//
+// Suggestions use a single graphic card, memory stick and drive. Incompatible parts are
+// rejected by `buy_*` as soon as they are bought, which prunes the rest of that combination.
+// Suggestions also keep the power headroom that `build_pc` doesn't insist on. Among the builds
+// that pass, the best one has the most processor cores, then the most graphic card memory,
+// then the lowest price.
+
+use super::{BuildError, PCBuilder, PartCatalog, PartSelection};
+
+#[derive(Clone, Debug, Default)]
+pub struct BuildConstraints {
+    pub budget: Option<u32>,
+    pub min_cores: u8,
+    pub min_gpu_memory: u32,
+    pub storage_interface: Option<String>,
+}
+
+// Higher is better, the price is negated so that cheaper builds win ties
+fn score(parts: &PartSelection) -> (u8, u32, i64) {
+    let cores = parts.processor.as_ref().map_or(0, |processor| processor.cores);
+    let gpu_memory = parts.graphic_cards.iter().map(|vga| vga.memory).sum();
+
+    (cores, gpu_memory, -(parts.total_price() as i64))
+}
+
+struct Search<'a> {
+    catalog: &'a PartCatalog,
+    constraints: &'a BuildConstraints,
+    best: Option<PartSelection>,
+}
+
+impl Search<'_> {
+    fn processors(&mut self, builder: &mut PCBuilder) {
+        for processor in self.catalog.processors.iter() {
+            if processor.cores < self.constraints.min_cores {
+                continue;
+            }
+
+            if builder.buy_processor(processor.clone()).is_ok() {
+                self.motherboards(builder);
+            }
+        }
+
+        builder.sell_processor();
+    }
+
+    fn motherboards(&mut self, builder: &mut PCBuilder) {
+        for motherboard in self.catalog.motherboards.iter() {
+            if builder.buy_motherboard(motherboard.clone()).is_ok() {
+                self.memory(builder);
+            }
+        }
+
+        builder.sell_motherboard();
+    }
+
+    fn memory(&mut self, builder: &mut PCBuilder) {
+        for ram in self.catalog.memory.iter() {
+            if builder.buy_memory(ram.clone()).is_ok() {
+                self.storage(builder);
+                let _ = builder.sell_memory(0);
+            }
+        }
+    }
+
+    fn storage(&mut self, builder: &mut PCBuilder) {
+        for storage in self.catalog.storage.iter() {
+            if let Some(interface) = self.constraints.storage_interface.as_ref() {
+                if storage.socket != *interface {
+                    continue;
+                }
+            }
+
+            if builder.buy_storage(storage.clone()).is_ok() {
+                self.graphic_cards(builder);
+                let _ = builder.sell_storage(0);
+            }
+        }
+    }
+
+    fn graphic_cards(&mut self, builder: &mut PCBuilder) {
+        for vga in self.catalog.graphic_cards.iter() {
+            if vga.memory < self.constraints.min_gpu_memory {
+                continue;
+            }
+
+            if builder.buy_graphic_card(vga.clone()).is_ok() {
+                self.power_supplies(builder);
+                let _ = builder.sell_graphic_card(0);
+            }
+        }
+    }
+
+    fn power_supplies(&mut self, builder: &mut PCBuilder) {
+        for psu in self.catalog.power_supplies.iter() {
+            if builder.buy_power_supply(psu.clone()).is_err() {
+                continue;
+            }
+
+            match builder.validate() {
+                Ok(power_budget) if power_budget.has_headroom() => {},
+                _ => continue,
+            }
+
+            let is_better = match self.best.as_ref() {
+                Some(best) => score(builder.parts()) > score(best),
+                None => true,
+            };
+
+            if is_better {
+                self.best = Some(builder.parts().clone());
+            }
+        }
+
+        builder.sell_power_supply();
+    }
+}
+
+// Returns a builder that is ready to be built, or `NoSuitableBuild` when
+// no combination in the catalog satisfies the constraints
+pub fn suggest_build(catalog: &PartCatalog, constraints: &BuildConstraints) -> Result<PCBuilder, BuildError> {
+    let mut builder = PCBuilder::new_builder();
+    builder.set_budget(constraints.budget);
+
+    let mut search = Search { catalog, constraints, best: None };
+    search.processors(&mut builder);
+
+    let best = search.best.ok_or(BuildError::NoSuitableBuild)?;
+    builder.parts = best;
+
+    Ok(builder)
+}
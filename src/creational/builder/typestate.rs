@@ -0,0 +1,97 @@
+// Typestate builder encodes which parts have been bought in its type.
+//
+// Every required part has its own type parameter that starts as `Missing` and becomes
+// `Supplied` after the part is bought. `build` is only implemented when every parameter
+// is `Supplied`, so forgetting a part is caught by the compiler instead of `build_pc`.
+//
+// Graphic cards are optional here since some processors come with integrated graphics.
+// Compatibility and power checks still happen at runtime, but they are deferred to `build`
+// so the purchases can be chained without handling an error on every step.
+
+use std::marker::PhantomData;
+
+use super::{
+    BuildError, CompatibilityRule, GraphicCard, Memory, Motherboard, PCBuilder, Part, PowerSupply,
+    Processor, Storage, PC,
+};
+
+pub struct Missing;
+pub struct Supplied;
+
+// Type parameters track the processor, motherboard, memory, storage and power supply respectively
+pub struct TypedPCBuilder<P, M, R, S, U> {
+    inner: PCBuilder,
+    state: PhantomData<(P, M, R, S, U)>,
+}
+
+impl TypedPCBuilder<Missing, Missing, Missing, Missing, Missing> {
+    pub fn new() -> Self {
+        TypedPCBuilder { inner: PCBuilder::new_builder(), state: PhantomData }
+    }
+}
+
+impl Default for TypedPCBuilder<Missing, Missing, Missing, Missing, Missing> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(dead_code)]
+impl<P, M, R, S, U> TypedPCBuilder<P, M, R, S, U> {
+    fn into_state<P2, M2, R2, S2, U2>(self) -> TypedPCBuilder<P2, M2, R2, S2, U2> {
+        TypedPCBuilder { inner: self.inner, state: PhantomData }
+    }
+
+    pub fn add_rule(mut self, rule: Box<dyn CompatibilityRule>) -> Self {
+        self.inner.add_rule(rule);
+
+        self
+    }
+
+    pub fn buy_processor(mut self, processor: Processor) -> TypedPCBuilder<Supplied, M, R, S, U> {
+        processor.place(&mut self.inner.parts);
+
+        self.into_state()
+    }
+
+    pub fn buy_motherboard(mut self, motherboard: Motherboard) -> TypedPCBuilder<P, Supplied, R, S, U> {
+        motherboard.place(&mut self.inner.parts);
+
+        self.into_state()
+    }
+
+    pub fn buy_memory(mut self, ram: Memory) -> TypedPCBuilder<P, M, Supplied, S, U> {
+        ram.place(&mut self.inner.parts);
+
+        self.into_state()
+    }
+
+    pub fn buy_storage(mut self, storage: Storage) -> TypedPCBuilder<P, M, R, Supplied, U> {
+        storage.place(&mut self.inner.parts);
+
+        self.into_state()
+    }
+
+    pub fn buy_power_supply(mut self, psu: PowerSupply) -> TypedPCBuilder<P, M, R, S, Supplied> {
+        psu.place(&mut self.inner.parts);
+
+        self.into_state()
+    }
+
+    pub fn buy_graphic_card(mut self, vga: GraphicCard) -> Self {
+        vga.place(&mut self.inner.parts);
+
+        self
+    }
+
+    // Falls back to the runtime builder, e.g. when parts need to be sold again
+    pub fn into_inner(self) -> PCBuilder {
+        self.inner
+    }
+}
+
+impl TypedPCBuilder<Supplied, Supplied, Supplied, Supplied, Supplied> {
+    pub fn build(&self) -> Result<PC, BuildError> {
+        self.inner.assemble()
+    }
+}
@@ -0,0 +1,40 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::DeliveryError;
+
+// Destination is an address in the form of "locality, region, country",
+// e.g. "Somewhere, Sumatra, Indonesia"
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Destination {
+    pub locality: String,
+    pub region: String,
+    pub country: String,
+}
+
+impl Destination {
+    pub fn new(locality: &str, region: &str, country: &str) -> Destination {
+        Destination { locality: locality.to_string(), region: region.to_string(), country: country.to_string() }
+    }
+}
+
+impl FromStr for Destination {
+    type Err = DeliveryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let segments: Vec<&str> = s.split(',').map(|segment| segment.trim()).collect();
+
+        match segments.as_slice() {
+            [locality, region, country] if segments.iter().all(|segment| !segment.is_empty()) => {
+                Ok(Destination::new(locality, region, country))
+            },
+            _ => Err(DeliveryError::InvalidDestination(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Destination {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}, {}, {}", self.locality, self.region, self.country)
+    }
+}
@@ -0,0 +1,126 @@
+// Vehicles are expensive, so the dealer doesn't hand out a brand new one for every delivery.
+//
+// The Fleet is a finite pool of vehicles built by the registry. A vehicle is leased
+// for a trip, stays busy while it's on the road, and is returned to the pool afterwards.
+// When every vehicle of a kind is busy, cargos have to wait for one to come back.
+//
+// A lease gives its vehicle back when it's dropped, like a pooled connection does, so a plan
+// that is only inspected and then thrown away doesn't keep its vehicles busy.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use super::{DeliveryError, Vehicle, VehicleRegistry};
+
+pub struct FleetVehicle {
+    pub id: String,
+    pub kind: &'static str,
+    vehicle: Rc<dyn Vehicle>,
+    busy: Rc<Cell<bool>>,
+}
+
+#[allow(dead_code)]
+impl FleetVehicle {
+    pub fn is_busy(&self) -> bool {
+        self.busy.get()
+    }
+}
+
+// Lease is a handle to a vehicle taken from the fleet. The vehicle goes back to the fleet
+// with `release`, or when the lease is dropped.
+pub struct Lease {
+    pub id: String,
+    pub kind: &'static str,
+    vehicle: Rc<dyn Vehicle>,
+    busy: Rc<Cell<bool>>,
+    // Once released, the vehicle may already be leased again, so dropping must leave it alone
+    released: Cell<bool>,
+}
+
+impl Lease {
+    pub fn vehicle(&self) -> &dyn Vehicle {
+        self.vehicle.as_ref()
+    }
+
+    pub fn release(&self) {
+        if !self.released.replace(true) {
+            self.busy.set(false);
+        }
+    }
+}
+
+impl Drop for Lease {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+#[derive(Default)]
+pub struct Fleet {
+    vehicles: Vec<FleetVehicle>,
+}
+
+#[allow(dead_code)]
+impl Fleet {
+    pub fn new() -> Fleet {
+        Fleet { vehicles: vec![] }
+    }
+
+    pub fn add(&mut self, kind: &'static str, vehicle: Box<dyn Vehicle>) -> &mut Fleet {
+        let id = format!("{}-{}", kind, self.owned(kind) + 1);
+        self.vehicles.push(FleetVehicle { id, kind, vehicle: Rc::from(vehicle), busy: Rc::new(Cell::new(false)) });
+
+        self
+    }
+
+    // Buys `count` vehicles of every kind from the registry
+    pub fn stock(registry: &VehicleRegistry, counts: &[(&str, usize)]) -> Fleet {
+        let mut fleet = Fleet::new();
+
+        for (name, count) in counts {
+            if let Some(kind) = registry.get(name) {
+                for _ in 0..*count {
+                    fleet.add(kind.name, kind.build());
+                }
+            }
+        }
+
+        fleet
+    }
+
+    pub fn vehicles(&self) -> &[FleetVehicle] {
+        &self.vehicles
+    }
+
+    pub fn owned(&self, kind: &str) -> usize {
+        self.vehicles.iter().filter(|vehicle| vehicle.kind == kind).count()
+    }
+
+    pub fn available(&self, kind: &str) -> usize {
+        self.vehicles.iter().filter(|vehicle| vehicle.kind == kind && !vehicle.is_busy()).count()
+    }
+
+    pub fn lease(&self, kind: &str) -> Result<Lease, DeliveryError> {
+        if self.owned(kind) == 0 {
+            return Err(DeliveryError::NotInFleet(kind.to_string()));
+        }
+
+        let vehicle = self.vehicles
+            .iter()
+            .find(|vehicle| vehicle.kind == kind && !vehicle.is_busy())
+            .ok_or_else(|| DeliveryError::NoVehicleAvailable(kind.to_string()))?;
+        vehicle.busy.set(true);
+
+        Ok(Lease {
+            id: vehicle.id.clone(),
+            kind: vehicle.kind,
+            vehicle: Rc::clone(&vehicle.vehicle),
+            busy: Rc::clone(&vehicle.busy),
+            released: Cell::new(false),
+        })
+    }
+
+    pub fn release(&self, lease: &Lease) {
+        lease.release();
+    }
+}
@@ -0,0 +1,493 @@
+// Factory method pattern is a creational design pattern to simplify object creation.
+// A factory method creates products to be used by clients.
+//
+// In this example, you are running a trustworthy transport company that boast
+// an armada of trucks to transport cargo intra-island as fast as possible. In recent years,
+// you want to expand your business to handle cross-island shippings. To ship across the seas,
+// your company need ships as there are no trucks that can run in the bottom of the ocean.
+//
+// Previously, your company directly deal with a well-known truck manufacturer (constructor). Although
+// the process is quite complex, it's something that your company can handle by itself.
+// However, you found out that dealing ships (constructor) are way more complex than trucks. Dealing with
+// ship manufacturer directly is not something your company can do anymore.
+//
+// To adhere this problem, your company contacts a vehicle dealer (factory) that delivers
+// correct vehicle as your company requested. The dealer looks up which vehicles it can offer
+// in a registry (see `registry.rs`), so new transport modes such as planes and trains can be
+// added without changing the dealer.
+//
+// Instead of sending one vehicle per cargo, cargos heading to the same region are consolidated
+// into as few vehicles as their payload capacity allows (see `planner.rs`).
+//
+// Shipping across islands takes more than a ship. The route planner (see `route.rs`) splits
+// the journey into legs: a truck to the port, ships across the sea and a truck to the door.
+// Every leg gets the vehicle that travels the right way (see `TransportMode`) and can carry
+// the cargo. `deliver_cargo` sends cargo for another island along such a route.
+//
+// Before anything is dispatched, every vehicle that can serve a cargo gives a quote on
+// its cost and travel time (see `quote.rs`), and the cheapest or fastest one is picked.
+//
+// The dealer doesn't build a vehicle for every trip either. It leases vehicles from a finite
+// fleet and takes them back once they're done (see `fleet.rs`); cargos wait when every
+// suitable vehicle is busy.
+//
+// Once dispatched, every delivery reports its progress to the subscribers of a tracker
+// and ends with a receipt (see `tracking.rs`).
+
+mod destination;
+mod fleet;
+mod planner;
+mod quote;
+mod registry;
+mod route;
+mod tracking;
+
+use std::cmp::Reverse;
+use std::error::Error;
+use std::fmt;
+
+pub use destination::Destination;
+#[allow(unused_imports)]
+pub use fleet::{Fleet, FleetVehicle, Lease};
+#[allow(unused_imports)]
+pub use route::{IslandMap, Leg, Route, RoutePlanner};
+#[allow(unused_imports)]
+pub use planner::{plan_dispatch, DispatchPlan, RoutedTrip, Trip};
+#[allow(unused_imports)]
+pub use quote::{DeliveryPolicy, Estimate, Quote, Shipment};
+#[allow(unused_imports)]
+pub use registry::{VehicleKind, VehicleRegistry};
+#[allow(unused_imports)]
+pub use tracking::{new_tracking_id, Delivery, DeliveryReceipt, Tracker, TrackingEvent, TrackingLog, TrackingStatus, TrackingSubscriber};
+
+// Vehicle is the product
+pub trait Vehicle {
+    fn name(&self) -> String;
+
+    // Moves the cargo to the destination, reporting the progress through the delivery
+    fn transport(&self, delivery: &Delivery);
+
+    fn deliver(&self, destination: String, weight: u32, tracker: &Tracker) -> DeliveryReceipt {
+        self.carry(Delivery::dispatch(tracker, self.name(), destination), weight)
+    }
+
+    // Carries one leg of a route under the tracking id of the whole shipment.
+    // Legs that end at a port finish there rather than being delivered.
+    fn deliver_leg(&self, tracking_id: &str, destination: String, to_port: bool, weight: u32, tracker: &Tracker) -> DeliveryReceipt {
+        let delivery = Delivery::dispatch_as(tracker, tracking_id.to_string(), self.name(), destination);
+
+        self.carry(if to_port { delivery.bound_for_port() } else { delivery }, weight)
+    }
+
+    fn carry(&self, delivery: Delivery, weight: u32) -> DeliveryReceipt {
+        if weight > self.capacity() {
+            return delivery.fail(DeliveryError::Overweight { weight, capacity: self.capacity() });
+        }
+
+        self.transport(&delivery);
+
+        delivery.complete()
+    }
+
+    // Maximum payload in kg
+    fn capacity(&self) -> u32;
+
+    // Cruising speed in km/h
+    fn speed(&self) -> f64;
+
+    // Liters of fuel burnt per km
+    fn fuel_consumption(&self) -> f64;
+
+    // Liters of fuel in a full tank
+    fn fuel_capacity(&self) -> u32;
+
+    // Extra cost for every ton of cargo carried per km
+    fn weight_surcharge(&self) -> f64;
+
+    fn mode(&self) -> TransportMode {
+        TransportMode::Land
+    }
+}
+
+// How a vehicle gets around. Vehicles that fly go straight to the destination
+// instead of following roads and sea lanes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransportMode {
+    Land,
+    Sea,
+    Air,
+}
+
+#[derive(Clone, Debug)]
+pub struct Cargo {
+    pub weight: u32,
+    pub destination: Destination,
+}
+
+#[derive(Clone, Debug)]
+pub enum DeliveryError {
+    InvalidDestination(String),
+    UnknownRegion(String),
+    NoRoute { from: String, to: String },
+    NoFlight(String),
+    NoSuitableVehicle(String),
+    NotInFleet(String),
+    NoVehicleAvailable(String),
+    Overweight { weight: u32, capacity: u32 },
+}
+
+impl fmt::Display for DeliveryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DeliveryError::InvalidDestination(destination) => write!(
+                f,
+                "{} is not in the form of \"locality, region, country\"",
+                destination,
+            ),
+            DeliveryError::UnknownRegion(region) => write!(f, "{} is not on any known island", region),
+            DeliveryError::NoRoute { from, to } => write!(f, "No sea lane connects {} and {}", from, to),
+            DeliveryError::NoFlight(country) => write!(f, "No flight goes to {}", country),
+            DeliveryError::NoSuitableVehicle(destination) => write!(f, "No vehicle can deliver to {}", destination),
+            DeliveryError::NotInFleet(kind) => write!(f, "The fleet has no {}", kind),
+            DeliveryError::NoVehicleAvailable(kind) => write!(f, "Every {} in the fleet is busy", kind),
+            DeliveryError::Overweight { weight, capacity } => write!(
+                f,
+                "The cargo weighs {}kg but the vehicle can only carry {}kg",
+                weight, capacity,
+            ),
+        }
+    }
+}
+
+impl Error for DeliveryError {}
+
+// Truck is a concrete product. Logically, a Truck is not something a non-specialist can assemble alone.
+#[allow(dead_code)]
+pub struct Truck {
+    brand: String,
+    wheel: u8,
+    fuel: u16,
+}
+
+impl Vehicle for Truck {
+    fn name(&self) -> String {
+        format!("{} truck", self.brand)
+    }
+
+    fn transport(&self, delivery: &Delivery) {
+        delivery.update(TrackingStatus::InTransit);
+        println!("Delivering cargo by truck to {}", delivery.destination());
+    }
+
+    fn capacity(&self) -> u32 {
+        800
+    }
+
+    fn speed(&self) -> f64 {
+        60.0
+    }
+
+    fn fuel_consumption(&self) -> f64 {
+        0.35
+    }
+
+    fn fuel_capacity(&self) -> u32 {
+        self.fuel as u32
+    }
+
+    fn weight_surcharge(&self) -> f64 {
+        0.1
+    }
+}
+
+// Ship is a concrete product. Same as Truck, a Ship isn't something a non-specialist can assemble alone.
+#[allow(dead_code)]
+pub struct Ship {
+    name: String,
+    fuel: u16,
+}
+
+impl Vehicle for Ship {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn transport(&self, delivery: &Delivery) {
+        delivery.update(TrackingStatus::InTransit);
+        println!("Shipping cargo across islands to {}", delivery.destination());
+    }
+
+    fn capacity(&self) -> u32 {
+        20000
+    }
+
+    fn speed(&self) -> f64 {
+        30.0
+    }
+
+    fn fuel_consumption(&self) -> f64 {
+        3.0
+    }
+
+    fn fuel_capacity(&self) -> u32 {
+        self.fuel as u32
+    }
+
+    fn weight_surcharge(&self) -> f64 {
+        0.02
+    }
+
+    fn mode(&self) -> TransportMode {
+        TransportMode::Sea
+    }
+}
+
+// Plane is a concrete product for destinations abroad
+#[allow(dead_code)]
+pub struct Plane {
+    model: String,
+    fuel: u16,
+}
+
+impl Vehicle for Plane {
+    fn name(&self) -> String {
+        format!("{} plane", self.model)
+    }
+
+    fn transport(&self, delivery: &Delivery) {
+        delivery.update(TrackingStatus::InTransit);
+        println!("Flying cargo overseas to {}", delivery.destination());
+    }
+
+    fn capacity(&self) -> u32 {
+        5000
+    }
+
+    fn speed(&self) -> f64 {
+        800.0
+    }
+
+    fn fuel_consumption(&self) -> f64 {
+        12.0
+    }
+
+    fn fuel_capacity(&self) -> u32 {
+        self.fuel as u32
+    }
+
+    fn weight_surcharge(&self) -> f64 {
+        2.0
+    }
+
+    fn mode(&self) -> TransportMode {
+        TransportMode::Air
+    }
+}
+
+// Train is a concrete product that runs on the railways of an island
+#[allow(dead_code)]
+pub struct Train {
+    line: String,
+    carriages: u8,
+    fuel: u16,
+}
+
+impl Vehicle for Train {
+    fn name(&self) -> String {
+        format!("{} train", self.line)
+    }
+
+    fn transport(&self, delivery: &Delivery) {
+        delivery.update(TrackingStatus::InTransit);
+        println!("Delivering cargo by train to {}", delivery.destination());
+    }
+
+    fn capacity(&self) -> u32 {
+        self.carriages as u32 * 1000
+    }
+
+    fn speed(&self) -> f64 {
+        80.0
+    }
+
+    fn fuel_consumption(&self) -> f64 {
+        1.5
+    }
+
+    fn fuel_capacity(&self) -> u32 {
+        self.fuel as u32
+    }
+
+    fn weight_surcharge(&self) -> f64 {
+        0.05
+    }
+}
+
+// VehicleDealer is a factory that simplifies vehicle creation
+pub struct VehicleDealer {
+    registry: VehicleRegistry,
+    planner: RoutePlanner,
+    fleet: Fleet,
+}
+
+#[allow(dead_code)]
+impl VehicleDealer {
+    pub fn new(registry: VehicleRegistry, planner: RoutePlanner, fleet: Fleet) -> VehicleDealer {
+        VehicleDealer { registry, planner, fleet }
+    }
+
+    pub fn fleet(&self) -> &Fleet {
+        &self.fleet
+    }
+
+    pub fn lease(&self, kind: &str) -> Result<Lease, DeliveryError> {
+        self.fleet.lease(kind)
+    }
+
+    pub fn release(&self, lease: &Lease) {
+        self.fleet.release(lease)
+    }
+
+    // Leases the vehicle with the highest priority that travels by `mode` and can carry the weight.
+    // When that kind is busy, the next suitable kind with a free vehicle is taken instead.
+    pub fn lease_for(&self, mode: TransportMode, weight: u32, to: &str) -> Result<Lease, DeliveryError> {
+        let mut kinds: Vec<&VehicleKind> = self.registry.kinds().iter().filter(|kind| self.fleet.owned(kind.name) > 0).collect();
+        kinds.sort_by_key(|kind| Reverse(kind.priority));
+
+        let mut busy = None;
+        let mut too_heavy = None;
+        for kind in kinds {
+            let vehicle = kind.build();
+            if vehicle.mode() != mode {
+                continue;
+            }
+            if weight > vehicle.capacity() {
+                too_heavy = Some(DeliveryError::Overweight { weight, capacity: vehicle.capacity() });
+
+                continue;
+            }
+
+            match self.fleet.lease(kind.name) {
+                Ok(lease) => return Ok(lease),
+                Err(err) => busy = busy.or(Some(err)),
+            }
+        }
+
+        // A busy vehicle comes back eventually, so that's the error worth waiting on
+        Err(busy.or(too_heavy).unwrap_or_else(|| DeliveryError::NoSuitableVehicle(to.to_string())))
+    }
+
+    pub fn plan_route(&self, destination: &Destination, weight: u32) -> Result<Route, DeliveryError> {
+        self.planner.plan(self, destination, weight)
+    }
+
+    // Whether the destination is on another island, so the cargo has to go along a route
+    pub fn crosses_sea(&self, destination: &Destination) -> bool {
+        self.planner.crosses_sea(destination)
+    }
+
+    // Asks every vehicle kind in the fleet that can carry the cargo for a quote
+    // and picks one according to the policy
+    pub fn quote(&self, cargo: &Cargo, policy: DeliveryPolicy) -> Result<Quote, DeliveryError> {
+        self.quote_among(cargo, policy, |kind| self.fleet.owned(kind) > 0)
+    }
+
+    // Like `quote`, but only kinds with a free vehicle right now are asked
+    pub fn quote_available(&self, cargo: &Cargo, policy: DeliveryPolicy) -> Result<Quote, DeliveryError> {
+        self.quote_among(cargo, policy, |kind| self.fleet.available(kind) > 0)
+    }
+
+    fn quote_among<F>(&self, cargo: &Cargo, policy: DeliveryPolicy, in_fleet: F) -> Result<Quote, DeliveryError>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let mut failure = None;
+        let mut quotes = vec![];
+
+        let kinds = self.registry
+            .kinds()
+            .iter()
+            .filter(|kind| in_fleet(kind.name) && kind.can_serve(&cargo.destination, self.planner.map()));
+
+        for kind in kinds {
+            let vehicle = kind.build();
+            if cargo.weight > vehicle.capacity() {
+                failure = Some(DeliveryError::Overweight { weight: cargo.weight, capacity: vehicle.capacity() });
+
+                continue;
+            }
+
+            // Every kind travels its own way, so one that can't get there doesn't stop the others
+            let distance = if vehicle.mode() == TransportMode::Air {
+                self.planner.air_distance(&cargo.destination)
+            } else {
+                self.planner.distance_to(&cargo.destination)
+            };
+            let distance = match distance {
+                Ok(distance) => distance,
+                Err(err) => {
+                    failure = Some(err);
+
+                    continue;
+                },
+            };
+
+            let estimate = quote::estimate(vehicle.as_ref(), cargo.weight, distance);
+            quotes.push(Quote { kind: kind.name, distance, cost: estimate.cost, hours: estimate.hours });
+        }
+
+        policy
+            .pick(quotes)
+            .ok_or_else(|| failure.unwrap_or_else(|| DeliveryError::NoSuitableVehicle(cargo.destination.to_string())))
+    }
+
+    pub fn get_kind(&self, name: &str) -> Option<&VehicleKind> {
+        self.registry.get(name)
+    }
+
+    pub fn get_suitable_kind(&self, destination: &Destination) -> Result<&VehicleKind, DeliveryError> {
+        self.registry.find(destination, self.planner.map()).ok_or_else(|| DeliveryError::NoSuitableVehicle(destination.to_string()))
+    }
+
+    pub fn get_suitable_vehicle(&self, destination: &Destination) -> Result<Lease, DeliveryError> {
+        self.lease(self.get_suitable_kind(destination)?.name)
+    }
+}
+
+impl Default for VehicleDealer {
+    fn default() -> Self {
+        let registry = VehicleRegistry::default();
+        let fleet = Fleet::stock(&registry, &[("truck", 2), ("rail", 1), ("ship", 2), ("plane", 1)]);
+
+        VehicleDealer::new(registry, RoutePlanner::default(), fleet)
+    }
+}
+
+// deliver_goods is the client, which will pick suitable vehicle based on cargo destination.
+// The executed plan is returned so the caller can look up the quote and receipt of every cargo.
+pub fn deliver_cargo(dealer: &VehicleDealer, cargos: Vec<Cargo>, policy: DeliveryPolicy, tracker: &Tracker) -> DispatchPlan {
+    let mut plan = plan_dispatch(dealer, cargos, policy);
+    plan.execute(dealer, tracker);
+
+    // Cargos that had to wait are dispatched once the vehicles are back
+    while !plan.queued.is_empty() {
+        let mut next = plan_dispatch(dealer, std::mem::take(&mut plan.queued), policy);
+        if next.is_empty() {
+            plan.merge(next);
+
+            break;
+        }
+
+        next.execute(dealer, tracker);
+        plan.merge(next);
+    }
+
+    for (cargo, err) in plan.rejected.iter() {
+        println!("Cannot deliver cargo to {}: {}", cargo.destination, err);
+    }
+    for cargo in plan.queued.iter() {
+        println!("Cargo to {} is still waiting for a free vehicle", cargo.destination);
+    }
+
+    plan
+}
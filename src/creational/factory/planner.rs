@@ -0,0 +1,172 @@
+// Sending one vehicle per cargo is a waste when several cargos go to the same region.
+//
+// The planner picks a vehicle kind for every cargo based on its quote, groups the cargos
+// by that vehicle kind and the region they go to,
+// then packs each group into as few vehicles as possible using first-fit decreasing:
+// the heaviest cargo goes first into the first vehicle that still has room for it.
+// Every vehicle is leased from the dealer's fleet. When the fleet runs out of free vehicles
+// of a kind, the remaining cargos of that group are quoted again among the kinds that still
+// have a free vehicle, and only queued until the vehicles are back when none of them fits.
+// Cargo for another island isn't packed with the rest: every such cargo travels along its own
+// route, leg by leg (see `route.rs`). Planes still fly overseas cargo straight to the door.
+// The resulting plan can be inspected before anything leaves the depot. Dropping a plan without
+// executing it gives its vehicles back to the fleet.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use super::{Cargo, DeliveryError, DeliveryPolicy, DeliveryReceipt, Lease, Quote, Route, Shipment, Tracker, TransportMode, VehicleDealer};
+
+pub struct Trip {
+    pub region: String,
+    pub lease: Lease,
+    pub shipments: Vec<Shipment>,
+}
+
+impl Trip {
+    pub fn load(&self) -> u32 {
+        self.shipments.iter().map(|shipment| shipment.cargo.weight).sum()
+    }
+
+    fn has_room_for(&self, cargo: &Cargo) -> bool {
+        self.load() + cargo.weight <= self.lease.vehicle().capacity()
+    }
+}
+
+// A cargo that crosses the sea along its route, with the receipts of the legs travelled so far
+pub struct RoutedTrip {
+    pub route: Route,
+    pub shipment: Shipment,
+    pub receipts: Vec<DeliveryReceipt>,
+}
+
+pub struct DispatchPlan {
+    pub trips: Vec<Trip>,
+    pub routed: Vec<RoutedTrip>,
+    // Cargos waiting for a vehicle of their kind to be returned to the fleet
+    pub queued: Vec<Cargo>,
+    pub rejected: Vec<(Cargo, DeliveryError)>,
+}
+
+impl DispatchPlan {
+    pub fn shipments(&self) -> impl Iterator<Item = &Shipment> {
+        self.trips
+            .iter()
+            .flat_map(|trip| trip.shipments.iter())
+            .chain(self.routed.iter().map(|trip| &trip.shipment))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.trips.is_empty() && self.routed.is_empty()
+    }
+
+    // Delivers every trip and returns the leased vehicles to the fleet
+    pub fn execute(&mut self, dealer: &VehicleDealer, tracker: &Tracker) {
+        for trip in self.trips.iter_mut() {
+            println!("Dispatching a {} ({}) to {} carrying {}kg of cargo", trip.lease.kind, trip.lease.id, trip.region, trip.load());
+
+            for shipment in trip.shipments.iter_mut() {
+                let cargo = &shipment.cargo;
+                shipment.receipt = Some(trip.lease.vehicle().deliver(cargo.destination.to_string(), cargo.weight, tracker));
+            }
+
+            dealer.release(&trip.lease);
+        }
+
+        // The last receipt of a route tells where the cargo ended up
+        for trip in self.routed.iter_mut() {
+            trip.receipts = trip.route.travel(dealer, tracker);
+            trip.shipment.receipt = trip.receipts.last().cloned();
+        }
+    }
+
+    pub fn merge(&mut self, other: DispatchPlan) {
+        self.trips.extend(other.trips);
+        self.routed.extend(other.routed);
+        self.queued.extend(other.queued);
+        self.rejected.extend(other.rejected);
+    }
+}
+
+fn crosses_sea(dealer: &VehicleDealer, cargo: &Cargo, quote: &Quote) -> bool {
+    let flies = dealer.get_kind(quote.kind).is_some_and(|kind| kind.build().mode() == TransportMode::Air);
+
+    !flies && dealer.crosses_sea(&cargo.destination)
+}
+
+pub fn plan_dispatch(dealer: &VehicleDealer, cargos: Vec<Cargo>, policy: DeliveryPolicy) -> DispatchPlan {
+    let mut plan = DispatchPlan { trips: vec![], routed: vec![], queued: vec![], rejected: vec![] };
+    let mut groups: BTreeMap<(&'static str, String), Vec<Shipment>> = BTreeMap::new();
+    // Cargos whose vehicle kind ran out of free vehicles
+    let mut fallback = vec![];
+
+    for cargo in cargos {
+        match dealer.quote(&cargo, policy) {
+            Ok(quote) if crosses_sea(dealer, &cargo, &quote) => {
+                match dealer.plan_route(&cargo.destination, cargo.weight) {
+                    Ok(route) => plan.routed.push(RoutedTrip { route, shipment: Shipment { cargo, quote, receipt: None }, receipts: vec![] }),
+                    Err(DeliveryError::NoVehicleAvailable(_)) => plan.queued.push(cargo),
+                    Err(err) => plan.rejected.push((cargo, err)),
+                }
+            },
+            Ok(quote) => {
+                let key = (quote.kind, cargo.destination.region.clone());
+                groups.entry(key).or_default().push(Shipment { cargo, quote, receipt: None });
+            },
+            Err(err) => plan.rejected.push((cargo, err)),
+        }
+    }
+
+    // Quotes already leave out vehicles that are too small for the cargo
+    for ((name, region), mut group) in groups {
+        let mut trips: Vec<Trip> = vec![];
+
+        group.sort_by_key(|shipment| Reverse(shipment.cargo.weight));
+        for shipment in group {
+            if let Some(trip) = trips.iter_mut().find(|trip| trip.has_room_for(&shipment.cargo)) {
+                trip.shipments.push(shipment);
+
+                continue;
+            }
+
+            match dealer.lease(name) {
+                Ok(lease) => trips.push(Trip { region: region.clone(), lease, shipments: vec![shipment] }),
+                Err(DeliveryError::NoVehicleAvailable(_)) => fallback.push(shipment),
+                Err(err) => plan.rejected.push((shipment.cargo, err)),
+            }
+        }
+
+        plan.trips.extend(trips);
+    }
+
+    // Waiting only makes sense when no other free vehicle can take the cargo
+    for shipment in fallback {
+        let cargo = shipment.cargo;
+        let quote = match dealer.quote_available(&cargo, policy) {
+            Ok(quote) if !crosses_sea(dealer, &cargo, &quote) => quote,
+            _ => {
+                plan.queued.push(cargo);
+
+                continue;
+            },
+        };
+
+        let region = cargo.destination.region.clone();
+        let shipment = Shipment { cargo, quote, receipt: None };
+        let same_trip = |trip: &&mut Trip| {
+            trip.lease.kind == shipment.quote.kind && trip.region == region && trip.has_room_for(&shipment.cargo)
+        };
+        if let Some(trip) = plan.trips.iter_mut().find(same_trip) {
+            trip.shipments.push(shipment);
+
+            continue;
+        }
+
+        match dealer.lease(shipment.quote.kind) {
+            Ok(lease) => plan.trips.push(Trip { region, lease, shipments: vec![shipment] }),
+            Err(_) => plan.queued.push(shipment.cargo),
+        }
+    }
+
+    plan
+}
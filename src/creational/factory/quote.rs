@@ -0,0 +1,68 @@
+// Every vehicle has its own price and pace. A plane is fast but burns a lot of fuel,
+// a ship is slow but carries heavy cargo cheaply.
+//
+// The estimate is based on the distance of the whole route (see `RoutePlanner::distance_to`),
+// or of the flight for vehicles that fly (see `RoutePlanner::air_distance`),
+// the vehicle speed, its fuel consumption, the stops it has to make when its tank runs dry,
+// and a surcharge for every ton of cargo carried per km. Among the vehicles that can serve
+// a destination, the caller decides whether the cheapest or the fastest one is picked.
+
+use super::{Cargo, DeliveryReceipt, Vehicle};
+
+// Price of fuel per liter
+pub const FUEL_PRICE: f64 = 1.5;
+// Time spent on every refueling stop, in hours
+pub const REFUEL_HOURS: f64 = 0.5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeliveryPolicy {
+    Cheapest,
+    Fastest,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Estimate {
+    pub cost: f64,
+    pub hours: f64,
+}
+
+pub fn estimate(vehicle: &dyn Vehicle, weight: u32, distance: u32) -> Estimate {
+    let distance = distance as f64;
+    let fuel = distance * vehicle.fuel_consumption();
+    let refuels = (fuel / vehicle.fuel_capacity() as f64).ceil().max(1.0) - 1.0;
+
+    Estimate {
+        cost: fuel * FUEL_PRICE + weight as f64 / 1000.0 * distance * vehicle.weight_surcharge(),
+        hours: distance / vehicle.speed() + refuels * REFUEL_HOURS,
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Quote {
+    pub kind: &'static str,
+    pub distance: u32,
+    pub cost: f64,
+    pub hours: f64,
+}
+
+impl DeliveryPolicy {
+    pub fn pick(self, quotes: Vec<Quote>) -> Option<Quote> {
+        quotes.into_iter().min_by(|a, b| {
+            let (a, b) = match self {
+                DeliveryPolicy::Cheapest => (a.cost, b.cost),
+                DeliveryPolicy::Fastest => (a.hours, b.hours),
+            };
+
+            a.total_cmp(&b)
+        })
+    }
+}
+
+// A cargo together with the quote of the vehicle that carries it,
+// and its receipt once it has been delivered
+#[derive(Clone, Debug)]
+pub struct Shipment {
+    pub cargo: Cargo,
+    pub quote: Quote,
+    pub receipt: Option<DeliveryReceipt>,
+}
@@ -0,0 +1,92 @@
+// VehicleRegistry is the dealer's catalog of transport modes.
+//
+// Each vehicle kind registers a predicate that tells whether it can serve a destination,
+// a priority, and a way to build the vehicle. The predicate is given the dealer's island map,
+// so vehicles are picked on the same map that routes are planned on. When several kinds can serve the same
+// destination, the one with the highest priority wins. Adding a new transport mode is
+// a matter of registering it, the dealer itself doesn't change.
+
+use super::route::HOME_ISLAND;
+use super::{Destination, IslandMap, Plane, Ship, Train, Truck, Vehicle};
+
+type ServePredicate = Box<dyn Fn(&Destination, &IslandMap) -> bool>;
+
+pub struct VehicleKind {
+    pub name: &'static str,
+    pub priority: u8,
+    can_serve: ServePredicate,
+    build: Box<dyn Fn() -> Box<dyn Vehicle>>,
+}
+
+impl VehicleKind {
+    pub fn can_serve(&self, destination: &Destination, map: &IslandMap) -> bool {
+        (self.can_serve)(destination, map)
+    }
+
+    pub fn build(&self) -> Box<dyn Vehicle> {
+        (self.build)()
+    }
+}
+
+pub struct VehicleRegistry {
+    kinds: Vec<VehicleKind>,
+}
+
+#[allow(dead_code)]
+impl VehicleRegistry {
+    pub fn new() -> VehicleRegistry {
+        VehicleRegistry { kinds: vec![] }
+    }
+
+    pub fn register<P, B>(&mut self, name: &'static str, priority: u8, can_serve: P, build: B)
+    where
+        P: Fn(&Destination, &IslandMap) -> bool + 'static,
+        B: Fn() -> Box<dyn Vehicle> + 'static,
+    {
+        self.kinds.push(VehicleKind { name, priority, can_serve: Box::new(can_serve), build: Box::new(build) });
+    }
+
+    pub fn kinds(&self) -> &[VehicleKind] {
+        &self.kinds
+    }
+
+    pub fn get(&self, name: &str) -> Option<&VehicleKind> {
+        self.kinds.iter().find(|kind| kind.name == name)
+    }
+
+    // Returns the kind with the highest priority that can serve the destination
+    pub fn find(&self, destination: &Destination, map: &IslandMap) -> Option<&VehicleKind> {
+        self.kinds
+            .iter()
+            .filter(|kind| kind.can_serve(destination, map))
+            .max_by_key(|kind| kind.priority)
+    }
+}
+
+impl Default for VehicleRegistry {
+    fn default() -> Self {
+        let mut registry = VehicleRegistry::new();
+        let on_home_island = |destination: &Destination, map: &IslandMap| {
+            map.island_of(destination).is_some_and(|island| island.name == HOME_ISLAND)
+        };
+        let on_other_island = |destination: &Destination, map: &IslandMap| {
+            map.island_of(destination).is_some_and(|island| island.name != HOME_ISLAND)
+        };
+
+        // Let's just assume that these are hard to manufacture
+        registry.register("truck", 20, on_home_island, || {
+            Box::new(Truck { brand: "Toyoda".to_string(), wheel: 4, fuel: 400 })
+        });
+        registry.register("rail", 10, on_home_island, || {
+            Box::new(Train { line: "Argo Bromo".to_string(), carriages: 12, fuel: 5000 })
+        });
+        registry.register("ship", 10, on_other_island, || {
+            Box::new(Ship { name: "S.S Anne".to_string(), fuel: 20000 })
+        });
+        registry.register("plane", 5, |destination, _| destination.country != "Indonesia", || {
+            Box::new(Plane { model: "Boeing 747-8F".to_string(), fuel: 30000 })
+        });
+
+        registry
+    }
+}
@@ -0,0 +1,311 @@
+// Cargos that go to another island don't travel by ship alone. A truck brings the cargo
+// to the nearest port, one or more ships carry it across the sea, and another truck
+// brings it from the arrival port to the door.
+//
+// IslandMap knows which regions belong to which island, where each island's port is,
+// and the sea lanes between those ports. RoutePlanner finds the shortest chain of sea lanes
+// between two islands and asks the dealer for a land or sea vehicle big enough for the cargo
+// on every leg.
+//
+// Planes don't care about islands, they fly straight from the depot to the destination
+// country. The planner knows the flight distance of every country it has a flight to.
+
+use super::{new_tracking_id, DeliveryError, DeliveryReceipt, Destination, Lease, Tracker, TransportMode, VehicleDealer};
+
+// Let's just say that your company is based in Java
+pub const HOME_ISLAND: &str = "Java";
+
+pub struct Island {
+    pub name: String,
+    pub port: String,
+    pub regions: Vec<String>,
+    // Average trucking distance between the port and a door on the island, in km
+    pub land_distance: u32,
+}
+
+pub struct SeaLane {
+    pub from: String,
+    pub to: String,
+    pub distance: u32,
+}
+
+pub struct IslandMap {
+    islands: Vec<Island>,
+    sea_lanes: Vec<SeaLane>,
+}
+
+#[allow(dead_code)]
+impl IslandMap {
+    pub fn new() -> IslandMap {
+        IslandMap { islands: vec![], sea_lanes: vec![] }
+    }
+
+    pub fn add_island(&mut self, name: &str, port: &str, regions: &[&str], land_distance: u32) -> &mut IslandMap {
+        self.islands.push(Island {
+            name: name.to_string(),
+            port: port.to_string(),
+            regions: regions.iter().map(|region| region.to_string()).collect(),
+            land_distance,
+        });
+
+        self
+    }
+
+    // Sea lanes go both ways
+    pub fn add_sea_lane(&mut self, from: &str, to: &str, distance: u32) -> &mut IslandMap {
+        self.sea_lanes.push(SeaLane { from: from.to_string(), to: to.to_string(), distance });
+
+        self
+    }
+
+    pub fn island(&self, name: &str) -> Option<&Island> {
+        self.islands.iter().find(|island| island.name == name)
+    }
+
+    pub fn island_of(&self, destination: &Destination) -> Option<&Island> {
+        self.islands.iter().find(|island| {
+            island.name == destination.region || island.regions.contains(&destination.region)
+        })
+    }
+
+    fn neighbours(&self, island: &str) -> Vec<(&str, u32)> {
+        self.sea_lanes
+            .iter()
+            .filter_map(|lane| {
+                if lane.from == island {
+                    Some((lane.to.as_str(), lane.distance))
+                } else if lane.to == island {
+                    Some((lane.from.as_str(), lane.distance))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    // Dijkstra's shortest path over the sea lanes. Returns the islands visited in order,
+    // including both ends, along with the distance of each hop.
+    pub fn sea_route(&self, from: &str, to: &str) -> Option<Vec<(&Island, u32)>> {
+        let mut distances: Vec<Option<u32>> = vec![None; self.islands.len()];
+        let mut previous: Vec<Option<(usize, u32)>> = vec![None; self.islands.len()];
+        let mut visited = vec![false; self.islands.len()];
+
+        let start = self.islands.iter().position(|island| island.name == from)?;
+        let end = self.islands.iter().position(|island| island.name == to)?;
+        distances[start] = Some(0);
+
+        loop {
+            let current = (0..self.islands.len())
+                .filter(|i| !visited[*i] && distances[*i].is_some())
+                .min_by_key(|i| distances[*i])?;
+
+            if current == end {
+                break;
+            }
+
+            visited[current] = true;
+            for (name, distance) in self.neighbours(&self.islands[current].name) {
+                let Some(next) = self.islands.iter().position(|island| island.name == name) else {
+                    continue;
+                };
+
+                let candidate = distances[current].unwrap() + distance;
+                if distances[next].is_none_or(|known| candidate < known) {
+                    distances[next] = Some(candidate);
+                    previous[next] = Some((current, distance));
+                }
+            }
+        }
+
+        let mut hops = vec![(&self.islands[end], 0)];
+        let mut current = end;
+        while let Some((prev, distance)) = previous[current] {
+            hops[0].1 = distance;
+            hops.insert(0, (&self.islands[prev], 0));
+            current = prev;
+        }
+
+        Some(hops)
+    }
+}
+
+impl Default for IslandMap {
+    fn default() -> Self {
+        let mut map = IslandMap::new();
+
+        map.add_island("Java", "Tanjung Priok", &["Banten", "Jakarta", "West Java", "Central Java", "Yogyakarta", "East Java"], 300)
+            .add_island("Sumatra", "Belawan", &["Aceh", "North Sumatra", "West Sumatra", "Riau", "Jambi", "South Sumatra", "Lampung"], 400)
+            .add_island("Bali", "Benoa", &[], 60)
+            .add_island("Kalimantan", "Balikpapan", &["West Kalimantan", "Central Kalimantan", "South Kalimantan", "East Kalimantan"], 500)
+            .add_island("Sulawesi", "Makassar", &["North Sulawesi", "Central Sulawesi", "South Sulawesi"], 350)
+            .add_sea_lane("Java", "Sumatra", 1400)
+            .add_sea_lane("Java", "Bali", 1000)
+            .add_sea_lane("Java", "Kalimantan", 1100)
+            .add_sea_lane("Bali", "Sulawesi", 800)
+            .add_sea_lane("Kalimantan", "Sulawesi", 600);
+
+        map
+    }
+}
+
+pub struct Leg {
+    pub from: String,
+    pub to: String,
+    pub distance: u32,
+    pub lease: Lease,
+}
+
+pub struct Route {
+    pub destination: Destination,
+    pub weight: u32,
+    pub legs: Vec<Leg>,
+}
+
+#[allow(dead_code)]
+impl Route {
+    pub fn distance(&self) -> u32 {
+        self.legs.iter().map(|leg| leg.distance).sum()
+    }
+
+    // Travels the legs in order under one tracking id. The cargo stays where it is once
+    // a leg fails, so the receipt of that leg is the last one. Every leased vehicle goes back
+    // to the fleet afterwards, whether it was used or not.
+    pub fn travel(&self, dealer: &VehicleDealer, tracker: &Tracker) -> Vec<DeliveryReceipt> {
+        println!("Route to {} ({}km)", self.destination, self.distance());
+        let tracking_id = new_tracking_id();
+        let mut receipts = vec![];
+
+        for (index, leg) in self.legs.iter().enumerate() {
+            println!("{} -> {} by {} ({}km)", leg.from, leg.to, leg.lease.id, leg.distance);
+
+            // Every leg but the last one ends at a port
+            let to_port = index + 1 < self.legs.len();
+            let receipt = leg.lease.vehicle().deliver_leg(&tracking_id, leg.to.clone(), to_port, self.weight, tracker);
+            let failed = receipt.is_failed();
+
+            receipts.push(receipt);
+            if failed {
+                break;
+            }
+        }
+
+        for leg in self.legs.iter() {
+            dealer.release(&leg.lease);
+        }
+
+        receipts
+    }
+}
+
+pub struct RoutePlanner {
+    map: IslandMap,
+    depot: Destination,
+    // Flight distance from the depot to each country, in km
+    flights: Vec<(String, u32)>,
+}
+
+#[allow(dead_code)]
+impl RoutePlanner {
+    pub fn new(map: IslandMap, depot: Destination) -> RoutePlanner {
+        RoutePlanner { map, depot, flights: vec![] }
+    }
+
+    pub fn add_flight(&mut self, country: &str, distance: u32) -> &mut RoutePlanner {
+        self.flights.push((country.to_string(), distance));
+
+        self
+    }
+
+    pub fn map(&self) -> &IslandMap {
+        &self.map
+    }
+
+    fn leg(&self, dealer: &VehicleDealer, mode: TransportMode, weight: u32, from: String, to: String, distance: u32) -> Result<Leg, DeliveryError> {
+        let lease = dealer.lease_for(mode, weight, &to)?;
+
+        Ok(Leg { from, to, distance, lease })
+    }
+
+    fn islands(&self, destination: &Destination) -> Result<(&Island, &Island), DeliveryError> {
+        let origin = self.map
+            .island_of(&self.depot)
+            .ok_or_else(|| DeliveryError::UnknownRegion(self.depot.region.clone()))?;
+        let target = self.map
+            .island_of(destination)
+            .ok_or_else(|| DeliveryError::UnknownRegion(destination.region.clone()))?;
+
+        Ok((origin, target))
+    }
+
+    // Whether the destination is on another island than the depot
+    pub fn crosses_sea(&self, destination: &Destination) -> bool {
+        self.islands(destination).is_ok_and(|(origin, target)| origin.name != target.name)
+    }
+
+    // Total distance from the depot to the destination, over land and sea
+    pub fn distance_to(&self, destination: &Destination) -> Result<u32, DeliveryError> {
+        let (origin, target) = self.islands(destination)?;
+        if origin.name == target.name {
+            return Ok(origin.land_distance);
+        }
+
+        let hops = self.map
+            .sea_route(&origin.name, &target.name)
+            .ok_or_else(|| DeliveryError::NoRoute { from: origin.name.clone(), to: target.name.clone() })?;
+        let sea_distance: u32 = hops.iter().map(|(_, distance)| distance).sum();
+
+        Ok(origin.land_distance + sea_distance + target.land_distance)
+    }
+
+    // Distance of the flight from the depot to the destination country
+    pub fn air_distance(&self, destination: &Destination) -> Result<u32, DeliveryError> {
+        self.flights
+            .iter()
+            .find(|(country, _)| *country == destination.country)
+            .map(|(_, distance)| *distance)
+            .ok_or_else(|| DeliveryError::NoFlight(destination.country.clone()))
+    }
+
+    // The vehicles of every leg are leased as soon as the route is planned. If one of them
+    // can't be leased, the leases of the other legs are dropped and their vehicles go back to the fleet.
+    pub fn plan(&self, dealer: &VehicleDealer, destination: &Destination, weight: u32) -> Result<Route, DeliveryError> {
+        Ok(Route { destination: destination.clone(), weight, legs: self.lease_legs(dealer, destination, weight)? })
+    }
+
+    fn lease_legs(&self, dealer: &VehicleDealer, destination: &Destination, weight: u32) -> Result<Vec<Leg>, DeliveryError> {
+        let (origin, target) = self.islands(destination)?;
+        let (land, sea) = (TransportMode::Land, TransportMode::Sea);
+
+        if origin.name == target.name {
+            return Ok(vec![self.leg(dealer, land, weight, self.depot.to_string(), destination.to_string(), origin.land_distance)?]);
+        }
+
+        let hops = self.map
+            .sea_route(&origin.name, &target.name)
+            .ok_or_else(|| DeliveryError::NoRoute { from: origin.name.clone(), to: target.name.clone() })?;
+
+        let mut legs = vec![self.leg(dealer, land, weight, self.depot.to_string(), origin.port.clone(), origin.land_distance)?];
+        for pair in hops.windows(2) {
+            let ((from, _), (to, distance)) = (pair[0], pair[1]);
+
+            legs.push(self.leg(dealer, sea, weight, from.port.clone(), to.port.clone(), distance)?);
+        }
+        legs.push(self.leg(dealer, land, weight, target.port.clone(), destination.to_string(), target.land_distance)?);
+
+        Ok(legs)
+    }
+}
+
+impl Default for RoutePlanner {
+    fn default() -> Self {
+        let mut planner = RoutePlanner::new(IslandMap::default(), Destination::new("Cikarang", "West Java", "Indonesia"));
+        planner
+            .add_flight("Singapore", 900)
+            .add_flight("Japan", 5800)
+            .add_flight("Australia", 5500)
+            .add_flight("Netherlands", 11300)
+            .add_flight("France", 11600);
+
+        planner
+    }
+}
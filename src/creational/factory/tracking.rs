@@ -0,0 +1,214 @@
+// Every delivery gets a tracking id and reports its progress as it goes: dispatched,
+// in transit, and finally delivered or failed. A delivery that only takes the cargo
+// to a port, like a leg of a route across the sea, ends at that port instead.
+// Every leg of a route shares one tracking id, so the whole journey can be followed.
+//
+// The Tracker forwards those events to whoever subscribed to it, e.g. a dashboard or a log,
+// and the vehicle hands back a DeliveryReceipt once the delivery is over.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::time::SystemTime;
+
+use uuid::Uuid;
+
+use super::DeliveryError;
+
+#[derive(Clone, Debug)]
+pub enum TrackingStatus {
+    Dispatched,
+    InTransit,
+    AtPort(String),
+    Delivered,
+    Failed(DeliveryError),
+}
+
+impl fmt::Display for TrackingStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TrackingStatus::Dispatched => write!(f, "dispatched"),
+            TrackingStatus::InTransit => write!(f, "in transit"),
+            TrackingStatus::AtPort(port) => write!(f, "at port {}", port),
+            TrackingStatus::Delivered => write!(f, "delivered"),
+            TrackingStatus::Failed(err) => write!(f, "failed: {}", err),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct TrackingEvent {
+    pub tracking_id: String,
+    pub vehicle: String,
+    pub destination: String,
+    pub status: TrackingStatus,
+    pub timestamp: SystemTime,
+}
+
+pub trait TrackingSubscriber {
+    fn notify(&self, event: &TrackingEvent);
+}
+
+impl<F: Fn(&TrackingEvent)> TrackingSubscriber for F {
+    fn notify(&self, event: &TrackingEvent) {
+        self(event)
+    }
+}
+
+// TrackingLog keeps every event it receives so they can be inspected later
+#[derive(Default)]
+pub struct TrackingLog {
+    events: RefCell<Vec<TrackingEvent>>,
+}
+
+#[allow(dead_code)]
+impl TrackingLog {
+    pub fn events(&self) -> Vec<TrackingEvent> {
+        self.events.borrow().clone()
+    }
+
+    pub fn history(&self, tracking_id: &str) -> Vec<TrackingEvent> {
+        self.events.borrow().iter().filter(|event| event.tracking_id == tracking_id).cloned().collect()
+    }
+}
+
+impl TrackingSubscriber for TrackingLog {
+    fn notify(&self, event: &TrackingEvent) {
+        self.events.borrow_mut().push(event.clone());
+    }
+}
+
+#[derive(Default)]
+pub struct Tracker<'a> {
+    subscribers: Vec<&'a dyn TrackingSubscriber>,
+}
+
+#[allow(dead_code)]
+impl<'a> Tracker<'a> {
+    pub fn new() -> Tracker<'a> {
+        Tracker { subscribers: vec![] }
+    }
+
+    pub fn subscribe(&mut self, subscriber: &'a dyn TrackingSubscriber) -> &mut Tracker<'a> {
+        self.subscribers.push(subscriber);
+
+        self
+    }
+
+    pub fn publish(&self, event: &TrackingEvent) {
+        for subscriber in self.subscribers.iter() {
+            subscriber.notify(event);
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct DeliveryReceipt {
+    pub tracking_id: String,
+    pub vehicle: String,
+    pub destination: String,
+    pub dispatched_at: SystemTime,
+    pub completed_at: SystemTime,
+    pub status: TrackingStatus,
+}
+
+#[allow(dead_code)]
+impl DeliveryReceipt {
+    pub fn is_delivered(&self) -> bool {
+        matches!(self.status, TrackingStatus::Delivered)
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self.status, TrackingStatus::Failed(_))
+    }
+}
+
+pub fn new_tracking_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+// Delivery is an ongoing delivery. Vehicles report their progress through it,
+// and it turns into a receipt once the cargo is delivered or the delivery fails.
+pub struct Delivery<'a, 't> {
+    tracker: &'a Tracker<'t>,
+    tracking_id: String,
+    vehicle: String,
+    destination: String,
+    // Whether the destination is a port rather than a door
+    to_port: bool,
+    dispatched_at: SystemTime,
+}
+
+impl<'a, 't> Delivery<'a, 't> {
+    pub fn dispatch(tracker: &'a Tracker<'t>, vehicle: String, destination: String) -> Delivery<'a, 't> {
+        Delivery::dispatch_as(tracker, new_tracking_id(), vehicle, destination)
+    }
+
+    // Dispatches under the tracking id of a shipment that is already on its way
+    pub fn dispatch_as(tracker: &'a Tracker<'t>, tracking_id: String, vehicle: String, destination: String) -> Delivery<'a, 't> {
+        let delivery = Delivery {
+            tracker,
+            tracking_id,
+            vehicle,
+            destination,
+            to_port: false,
+            dispatched_at: SystemTime::now(),
+        };
+        delivery.update(TrackingStatus::Dispatched);
+
+        delivery
+    }
+
+    pub fn bound_for_port(mut self) -> Delivery<'a, 't> {
+        self.to_port = true;
+
+        self
+    }
+
+    pub fn destination(&self) -> &str {
+        &self.destination
+    }
+
+    // The port the cargo arrives at, if it's headed to a port at all
+    pub fn port(&self) -> Option<&str> {
+        self.to_port.then_some(self.destination.as_str())
+    }
+
+    pub fn update(&self, status: TrackingStatus) {
+        self.tracker.publish(&TrackingEvent {
+            tracking_id: self.tracking_id.clone(),
+            vehicle: self.vehicle.clone(),
+            destination: self.destination.clone(),
+            status,
+            timestamp: SystemTime::now(),
+        });
+    }
+
+    fn finish(self, status: TrackingStatus) -> DeliveryReceipt {
+        self.update(status.clone());
+
+        DeliveryReceipt {
+            tracking_id: self.tracking_id,
+            vehicle: self.vehicle,
+            destination: self.destination,
+            dispatched_at: self.dispatched_at,
+            completed_at: SystemTime::now(),
+            status,
+        }
+    }
+
+    // Ends at the port if the delivery is bound for one, at the door otherwise
+    pub fn complete(self) -> DeliveryReceipt {
+        let status = match self.port() {
+            Some(port) => TrackingStatus::AtPort(port.to_string()),
+            None => TrackingStatus::Delivered,
+        };
+
+        self.finish(status)
+    }
+
+    pub fn fail(self, err: DeliveryError) -> DeliveryReceipt {
+        self.finish(TrackingStatus::Failed(err))
+    }
+}
@@ -0,0 +1,79 @@
+// Prototype is a design pattern that allows you to copy existing
+// object without being too dependant of the source class
+//
+// The main problem of naive copying:
+// 1. We don't know the value of private methods
+// 2. Cannot handle interface (trait) well
+// 3. Coupling of class
+//
+// In Rust, we have the `Clone` trait, so we don't have to implement
+// it from scratch. This is very convenient due to Rust ownership model.
+//
+// `Clone` can't be used on a trait object though, since the compiler doesn't know
+// what's behind the `Box<dyn Prototype>`. Every prototype gets `clone_box` for free
+// instead, which clones the concrete value and boxes it again.
+//
+// Prototypes are kept under a name in a registry (see `registry.rs`), which
+// stamps out fresh copies of them on request.
+//
+// FAQ
+//
+// 1. Copy vs Clone?
+// - Copy is implicit and inexpensive bit-wise copy while Clone performs full object duplication
+//   Rust doesn't allow re-implementation of copy. If you want to copy a struct with props
+//   that doesn't allow Copy, you have to Clone it.
+
+mod registry;
+
+use std::any::Any;
+
+#[allow(unused_imports)]
+pub use registry::{PrototypeError, PrototypeRegistry};
+
+// Implemented for every `Clone` prototype, there's no need to write it by hand
+pub trait PrototypeClone {
+    fn clone_box(&self) -> Box<dyn Prototype>;
+}
+
+impl<T: Prototype + Clone> PrototypeClone for T {
+    fn clone_box(&self) -> Box<dyn Prototype> {
+        Box::new(self.clone())
+    }
+}
+
+pub trait Prototype: PrototypeClone + Any {
+    fn describe(&self) -> String;
+}
+
+impl Clone for Box<dyn Prototype> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct User {
+    pub name: String,
+    pub age: u8,
+}
+
+impl Prototype for User {
+    fn describe(&self) -> String {
+        format!("{}, {} years old", self.name, self.age)
+    }
+}
+
+// Cloning a team clones its members too, so copies never share them
+#[derive(Clone, Debug, PartialEq)]
+pub struct Team {
+    pub name: String,
+    pub members: Vec<User>,
+}
+
+impl Prototype for Team {
+    fn describe(&self) -> String {
+        let members: Vec<&str> = self.members.iter().map(|member| member.name.as_str()).collect();
+
+        format!("{} with {}", self.name, members.join(", "))
+    }
+}
@@ -0,0 +1,89 @@
+// PrototypeRegistry keeps preconfigured prototypes under a name.
+//
+// Asking for a name returns a fresh clone, so whatever the caller does with it never
+// changes the prototype. A clone can also be adjusted before it's handed out, which
+// saves registering a new prototype for every small variation.
+
+use std::any::{type_name, Any};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use super::Prototype;
+
+#[derive(Debug, PartialEq)]
+pub enum PrototypeError {
+    UnknownPrototype(String),
+    WrongType { name: String, expected: &'static str },
+}
+
+impl fmt::Display for PrototypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PrototypeError::UnknownPrototype(name) => write!(f, "No prototype is registered as {}", name),
+            PrototypeError::WrongType { name, expected } => write!(f, "Prototype {} is not a {}", name, expected),
+        }
+    }
+}
+
+impl Error for PrototypeError {}
+
+#[derive(Clone, Default)]
+pub struct PrototypeRegistry {
+    prototypes: HashMap<String, Box<dyn Prototype>>,
+}
+
+#[allow(dead_code)]
+impl PrototypeRegistry {
+    pub fn new() -> PrototypeRegistry {
+        PrototypeRegistry::default()
+    }
+
+    // Registering under a name that's taken replaces the old prototype and returns it
+    pub fn register(&mut self, name: &str, prototype: Box<dyn Prototype>) -> Option<Box<dyn Prototype>> {
+        self.prototypes.insert(name.to_string(), prototype)
+    }
+
+    pub fn unregister(&mut self, name: &str) -> Option<Box<dyn Prototype>> {
+        self.prototypes.remove(name)
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.prototypes.keys().map(|name| name.as_str()).collect();
+        names.sort();
+
+        names
+    }
+
+    pub fn create(&self, name: &str) -> Result<Box<dyn Prototype>, PrototypeError> {
+        self.prototypes
+            .get(name)
+            .map(|prototype| prototype.clone_box())
+            .ok_or_else(|| PrototypeError::UnknownPrototype(name.to_string()))
+    }
+
+    // Clones the prototype as the concrete type it was registered as
+    pub fn create_as<T: Prototype>(&self, name: &str) -> Result<T, PrototypeError> {
+        let prototype: Box<dyn Any> = self.create(name)?;
+
+        prototype
+            .downcast::<T>()
+            .map(|prototype| *prototype)
+            .map_err(|_| PrototypeError::WrongType {
+                name: name.to_string(),
+                expected: type_name::<T>().rsplit("::").next().unwrap_or_default(),
+            })
+    }
+
+    // Clones the prototype and lets the caller override some of its fields
+    pub fn create_with<T, F>(&self, name: &str, overrides: F) -> Result<T, PrototypeError>
+    where
+        T: Prototype,
+        F: FnOnce(&mut T),
+    {
+        let mut prototype = self.create_as::<T>(name)?;
+        overrides(&mut prototype);
+
+        Ok(prototype)
+    }
+}
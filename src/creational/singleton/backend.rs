@@ -0,0 +1,255 @@
+// A backend opens connections to the actual database server.
+//
+// MemoryBackend is a fake backend that keeps everything in memory. Its connections run
+// queries against tables that live as long as the backend does (see `store.rs`), it remembers
+// every query they executed, and connections can be told to go bad so the pool's health checks
+// can be exercised without a real server.
+//
+// A transaction works on its own copy of the tables, so nobody else sees its changes
+// before it's committed. Committing runs the transaction's changes again on the shared tables,
+// which keeps whatever other connections wrote in the meantime. If they no longer apply,
+// the commit fails and the shared tables are left untouched.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::sql::{self, Command};
+use super::store::Store;
+use super::{DbError, QueryResult, Statement, Value};
+
+pub trait Connection: Send {
+    fn execute(&mut self, statement: &Statement, params: &[Value]) -> Result<QueryResult, DbError>;
+
+    fn begin(&mut self) -> Result<(), DbError>;
+    fn commit(&mut self) -> Result<(), DbError>;
+    fn rollback(&mut self) -> Result<(), DbError>;
+
+    // Returns false when the connection is no longer usable
+    fn ping(&mut self) -> bool;
+}
+
+pub trait Backend: Send + Sync {
+    fn connect(&self) -> Result<Box<dyn Connection>, DbError>;
+}
+
+#[derive(Clone, Default)]
+pub struct MemoryBackend {
+    store: Arc<Mutex<Store>>,
+    log: Arc<Mutex<Vec<String>>>,
+    opened: Arc<AtomicUsize>,
+    // Bumped every time the connections are broken, connections from an older generation are dead
+    generation: Arc<AtomicUsize>,
+}
+
+#[allow(dead_code)]
+impl MemoryBackend {
+    pub fn new() -> MemoryBackend {
+        MemoryBackend::default()
+    }
+
+    // A copy of the tables as they are now
+    pub fn snapshot(&self) -> Store {
+        self.store.lock().unwrap().clone()
+    }
+
+    // Every query committed so far, over all connections
+    pub fn log(&self) -> Vec<String> {
+        self.log.lock().unwrap().clone()
+    }
+
+    // Number of connections opened so far
+    pub fn opened(&self) -> usize {
+        self.opened.load(Ordering::SeqCst)
+    }
+
+    // Makes every open connection fail its health check, as if the server restarted
+    pub fn break_connections(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl Backend for MemoryBackend {
+    fn connect(&self) -> Result<Box<dyn Connection>, DbError> {
+        self.opened.fetch_add(1, Ordering::SeqCst);
+
+        Ok(Box::new(Database {
+            store: Arc::clone(&self.store),
+            log: Arc::clone(&self.log),
+            generation: self.generation.load(Ordering::SeqCst),
+            current_generation: Arc::clone(&self.generation),
+            transaction: None,
+        }))
+    }
+}
+
+// Database is a connection to the in-memory backend
+pub struct Database {
+    store: Arc<Mutex<Store>>,
+    log: Arc<Mutex<Vec<String>>>,
+    generation: usize,
+    current_generation: Arc<AtomicUsize>,
+    transaction: Option<OpenTransaction>,
+}
+
+// The tables and queries of the ongoing transaction, and the changes to run again on commit
+struct OpenTransaction {
+    store: Store,
+    changes: Vec<Command>,
+    queries: Vec<String>,
+}
+
+impl Database {
+    fn check_alive(&mut self) -> Result<(), DbError> {
+        if !self.ping() {
+            return Err(DbError::ConnectionLost);
+        }
+
+        Ok(())
+    }
+}
+
+impl Connection for Database {
+    fn execute(&mut self, statement: &Statement, params: &[Value]) -> Result<QueryResult, DbError> {
+        self.check_alive()?;
+
+        let command = sql::parse(statement.sql(), params)?;
+        let params: Vec<String> = params.iter().map(|param| param.to_string()).collect();
+        let query = format!("{} [{}]", statement.sql(), params.join(", "));
+
+        match self.transaction.as_mut() {
+            Some(transaction) => {
+                let change = (!matches!(command, Command::Select { .. })).then(|| command.clone());
+                let result = transaction.store.execute(command)?;
+                transaction.changes.extend(change);
+                transaction.queries.push(query);
+
+                Ok(result)
+            },
+            None => {
+                let result = self.store.lock().unwrap().execute(command)?;
+                self.log.lock().unwrap().push(query);
+
+                Ok(result)
+            },
+        }
+    }
+
+    fn begin(&mut self) -> Result<(), DbError> {
+        self.check_alive()?;
+        if self.transaction.is_some() {
+            return Err(DbError::TransactionActive);
+        }
+
+        let store = self.store.lock().unwrap().clone();
+        self.transaction = Some(OpenTransaction { store, changes: vec![], queries: vec![] });
+
+        Ok(())
+    }
+
+    fn commit(&mut self) -> Result<(), DbError> {
+        self.check_alive()?;
+        let transaction = self.transaction.take().ok_or(DbError::NoTransaction)?;
+
+        // The changes are applied to a copy first, so a change that fails leaves nothing behind
+        let mut store = self.store.lock().unwrap();
+        let mut committed = store.clone();
+        for change in transaction.changes {
+            committed.execute(change)?;
+        }
+        *store = committed;
+        drop(store);
+
+        self.log.lock().unwrap().extend(transaction.queries);
+
+        Ok(())
+    }
+
+    fn rollback(&mut self) -> Result<(), DbError> {
+        self.transaction.take().ok_or(DbError::NoTransaction)?;
+
+        Ok(())
+    }
+
+    fn ping(&mut self) -> bool {
+        self.generation == self.current_generation.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exec(connection: &mut dyn Connection, query: &str) -> Result<QueryResult, DbError> {
+        connection.execute(&Statement::prepare(query)?, &[])
+    }
+
+    fn names(backend: &MemoryBackend) -> Vec<Value> {
+        let table = backend.snapshot().table("users").cloned().unwrap();
+
+        table.rows.into_iter().map(|row| row[0].clone()).collect()
+    }
+
+    fn backend() -> MemoryBackend {
+        let backend = MemoryBackend::new();
+        exec(backend.connect().unwrap().as_mut(), "CREATE TABLE users (name TEXT)").unwrap();
+
+        backend
+    }
+
+    #[test]
+    fn commit_keeps_what_others_wrote_in_the_meantime() {
+        let backend = backend();
+        let mut first = backend.connect().unwrap();
+        let mut second = backend.connect().unwrap();
+
+        first.begin().unwrap();
+        exec(first.as_mut(), "INSERT INTO users VALUES ('John')").unwrap();
+        exec(second.as_mut(), "INSERT INTO users VALUES ('Jane')").unwrap();
+        assert_eq!(names(&backend), vec![Value::from("Jane")]);
+
+        first.commit().unwrap();
+        assert_eq!(names(&backend), vec![Value::from("Jane"), Value::from("John")]);
+    }
+
+    #[test]
+    fn a_commit_that_no_longer_applies_changes_nothing() {
+        let backend = backend();
+        let mut first = backend.connect().unwrap();
+        let mut second = backend.connect().unwrap();
+
+        first.begin().unwrap();
+        exec(first.as_mut(), "INSERT INTO users VALUES ('John')").unwrap();
+        exec(first.as_mut(), "CREATE TABLE orders (id INTEGER)").unwrap();
+        exec(second.as_mut(), "CREATE TABLE orders (id INTEGER)").unwrap();
+
+        assert!(matches!(first.commit(), Err(DbError::TableExists(_))));
+        assert!(names(&backend).is_empty());
+        assert!(matches!(first.rollback(), Err(DbError::NoTransaction)));
+    }
+
+    #[test]
+    fn rollback_throws_the_changes_away() {
+        let backend = backend();
+        let mut connection = backend.connect().unwrap();
+
+        connection.begin().unwrap();
+        assert!(matches!(connection.begin(), Err(DbError::TransactionActive)));
+        exec(connection.as_mut(), "INSERT INTO users VALUES ('John')").unwrap();
+        connection.rollback().unwrap();
+
+        assert!(names(&backend).is_empty());
+        assert_eq!(backend.log(), vec!["CREATE TABLE users (name TEXT) []"]);
+    }
+
+    #[test]
+    fn broken_connections_fail_their_health_check() {
+        let backend = backend();
+        let mut old = backend.connect().unwrap();
+
+        backend.break_connections();
+
+        assert!(!old.ping());
+        assert!(matches!(exec(old.as_mut(), "SELECT * FROM users"), Err(DbError::ConnectionLost)));
+        assert!(backend.connect().unwrap().ping());
+    }
+}
@@ -0,0 +1,199 @@
+// Monitor watches every query run through `exec` or a transaction.
+//
+// It times each query, counts them by the kind of statement, and measures how long callers
+// waited for the pool to hand them a connection. Queries that never ran, because their values
+// didn't match or no connection was free in time, count as failed. Queries that take longer than the slow query
+// threshold are kept, up to a limit, so they can be looked at later. Logging prints every query
+// with its timing as it finishes.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+pub struct MonitorConfig {
+    pub slow_threshold: Duration,
+    // Slow queries kept at most, the oldest ones are dropped first
+    pub max_slow_queries: usize,
+    pub log_queries: bool,
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        MonitorConfig { slow_threshold: Duration::from_millis(100), max_slow_queries: 100, log_queries: false }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StatementKind {
+    Create,
+    Insert,
+    Select,
+    Update,
+    Delete,
+    Other,
+}
+
+impl StatementKind {
+    // Tells the kind apart by the first word of the query
+    pub fn of(sql: &str) -> StatementKind {
+        let keyword = sql.split_whitespace().next().unwrap_or("").to_uppercase();
+
+        match keyword.as_str() {
+            "CREATE" => StatementKind::Create,
+            "INSERT" => StatementKind::Insert,
+            "SELECT" => StatementKind::Select,
+            "UPDATE" => StatementKind::Update,
+            "DELETE" => StatementKind::Delete,
+            _ => StatementKind::Other,
+        }
+    }
+}
+
+impl fmt::Display for StatementKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            StatementKind::Create => "CREATE",
+            StatementKind::Insert => "INSERT",
+            StatementKind::Select => "SELECT",
+            StatementKind::Update => "UPDATE",
+            StatementKind::Delete => "DELETE",
+            StatementKind::Other => "OTHER",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryRecord {
+    pub sql: String,
+    pub kind: StatementKind,
+    pub duration: Duration,
+    pub failed: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct KindStats {
+    pub count: usize,
+    pub errors: usize,
+    pub total_time: Duration,
+    pub max_time: Duration,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct QueryStats {
+    pub by_kind: BTreeMap<StatementKind, KindStats>,
+    // Times a connection was checked out of the pool, and how long that took
+    pub checkouts: usize,
+    pub total_wait: Duration,
+    pub max_wait: Duration,
+    pub slow_queries: usize,
+}
+
+#[allow(dead_code)]
+impl QueryStats {
+    pub fn queries(&self) -> usize {
+        self.by_kind.values().map(|stats| stats.count).sum()
+    }
+
+    pub fn errors(&self) -> usize {
+        self.by_kind.values().map(|stats| stats.errors).sum()
+    }
+
+    pub fn total_time(&self) -> Duration {
+        self.by_kind.values().map(|stats| stats.total_time).sum()
+    }
+}
+
+struct MonitorState {
+    config: MonitorConfig,
+    stats: QueryStats,
+    slow: VecDeque<QueryRecord>,
+}
+
+pub struct Monitor {
+    state: Mutex<MonitorState>,
+}
+
+#[allow(dead_code)]
+impl Monitor {
+    pub fn new(config: MonitorConfig) -> Monitor {
+        Monitor { state: Mutex::new(MonitorState { config, stats: QueryStats::default(), slow: VecDeque::new() }) }
+    }
+
+    pub fn config(&self) -> MonitorConfig {
+        self.state.lock().unwrap().config.clone()
+    }
+
+    pub fn set_slow_threshold(&self, threshold: Duration) {
+        self.state.lock().unwrap().config.slow_threshold = threshold;
+    }
+
+    pub fn set_logging(&self, log_queries: bool) {
+        self.state.lock().unwrap().config.log_queries = log_queries;
+    }
+
+    pub fn record_checkout(&self, wait: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.stats.checkouts += 1;
+        state.stats.total_wait += wait;
+        state.stats.max_wait = state.stats.max_wait.max(wait);
+    }
+
+    pub fn record_query(&self, sql: &str, duration: Duration, failed: bool) {
+        let kind = StatementKind::of(sql);
+        let mut state = self.state.lock().unwrap();
+
+        let stats = state.stats.by_kind.entry(kind).or_default();
+        stats.count += 1;
+        stats.total_time += duration;
+        stats.max_time = stats.max_time.max(duration);
+        if failed {
+            stats.errors += 1;
+        }
+
+        if state.config.log_queries {
+            println!("[{} in {:?}{}] {}", kind, duration, if failed { ", failed" } else { "" }, sql);
+        }
+
+        if duration >= state.config.slow_threshold {
+            state.stats.slow_queries += 1;
+            state.slow.push_back(QueryRecord { sql: sql.to_string(), kind, duration, failed });
+            while state.slow.len() > state.config.max_slow_queries {
+                state.slow.pop_front();
+            }
+        }
+    }
+
+    // Counts a query that failed before it ran, so it has no timing and is never slow
+    pub fn record_failure(&self, sql: &str) {
+        let kind = StatementKind::of(sql);
+        let mut state = self.state.lock().unwrap();
+
+        let stats = state.stats.by_kind.entry(kind).or_default();
+        stats.count += 1;
+        stats.errors += 1;
+
+        if state.config.log_queries {
+            println!("[{} rejected] {}", kind, sql);
+        }
+    }
+
+    pub fn stats(&self) -> QueryStats {
+        self.state.lock().unwrap().stats.clone()
+    }
+
+    // The slow queries kept so far, oldest first
+    pub fn slow_queries(&self) -> Vec<QueryRecord> {
+        self.state.lock().unwrap().slow.iter().cloned().collect()
+    }
+
+    // Forgets everything recorded so far but keeps the configuration
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.stats = QueryStats::default();
+        state.slow.clear();
+    }
+}
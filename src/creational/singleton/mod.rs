@@ -0,0 +1,155 @@
+// Singleton pattern is a design pattern that ensures an object is only
+// created once in the application.
+//
+// Generally, it's best to use a connection pool to manage database connections,
+// so that's what the application shares: one pool (see `pool.rs`) that lends out
+// connections to whoever needs one. Queries no longer wait on a single lock, only on
+// the pool when all of its connections are busy.
+//
+// Queries bind their values instead of pasting them into the SQL and return rows
+// (see `query.rs`), and several queries can run as one transaction (see `transaction.rs`).
+//
+// Behind the pool sits an in-memory database that understands a small subset of SQL
+// (see `sql.rs`) and keeps its tables for as long as the process runs.
+//
+// Every query is timed and counted, and the slow ones are kept for later (see `metrics.rs`).
+//
+// Rust doesn't support mutable global variable in safe code. The pool is kept in a
+// `Singleton` (see `registry.rs`) that creates it on first use, and can swap it for a pool
+// over a fake backend or reset it between tests.
+
+mod backend;
+mod metrics;
+mod pool;
+mod query;
+mod registry;
+mod sql;
+mod store;
+mod transaction;
+
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[allow(unused_imports)]
+pub use backend::{Backend, Connection, Database, MemoryBackend};
+#[allow(unused_imports)]
+pub use metrics::{KindStats, Monitor, MonitorConfig, QueryRecord, QueryStats, StatementKind};
+#[allow(unused_imports)]
+pub use pool::{Pool, PoolConfig, PoolStatus, PooledConnection};
+#[allow(unused_imports)]
+pub use query::{QueryResult, Row, Statement, Value};
+#[allow(unused_imports)]
+pub use registry::{services, Registry, RegistryError, Singleton};
+#[allow(unused_imports)]
+pub use store::{Store, Table};
+#[allow(unused_imports)]
+pub use transaction::Transaction;
+
+// The pool is configured by the `PoolConfig` in the registry, if one was registered in time
+static POOL: Singleton<Pool> = Singleton::new(|| {
+    let config = services().get::<PoolConfig>().map(|config| (*config).clone()).unwrap_or_default();
+
+    Pool::new(Box::new(MemoryBackend::new()), config)
+});
+
+static MONITOR: Singleton<Monitor> = Singleton::new(|| {
+    Monitor::new(services().get::<MonitorConfig>().map(|config| (*config).clone()).unwrap_or_default())
+});
+
+#[derive(Debug)]
+pub enum DbError {
+    Timeout(Duration),
+    ConnectionLost,
+    Syntax(String),
+    ParameterCount { expected: usize, given: usize },
+    TransactionActive,
+    NoTransaction,
+    UnknownTable(String),
+    UnknownColumn(String),
+    TableExists(String),
+    TypeMismatch { column: String, value: Value },
+}
+
+impl fmt::Display for DbError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DbError::Timeout(timeout) => write!(f, "No connection was returned to the pool within {:?}", timeout),
+            DbError::ConnectionLost => write!(f, "The connection to the database was lost"),
+            DbError::Syntax(reason) => write!(f, "Syntax error: {}", reason),
+            DbError::ParameterCount { expected, given } => write!(
+                f,
+                "The query expects {} values but {} were given",
+                expected, given,
+            ),
+            DbError::TransactionActive => write!(f, "A transaction is already in progress"),
+            DbError::NoTransaction => write!(f, "There is no transaction in progress"),
+            DbError::UnknownTable(table) => write!(f, "Table {} doesn't exist", table),
+            DbError::UnknownColumn(column) => write!(f, "Column {} doesn't exist", column),
+            DbError::TableExists(table) => write!(f, "Table {} already exists", table),
+            DbError::TypeMismatch { column, value } => write!(f, "{} doesn't fit in column {}", value, column),
+        }
+    }
+}
+
+impl Error for DbError {}
+
+pub fn pool() -> Arc<Pool> {
+    POOL.get()
+}
+
+// Makes every query go through another pool, returning the one used until now
+pub fn replace_pool(pool: Pool) -> Option<Arc<Pool>> {
+    POOL.replace(pool)
+}
+
+// Throws the pool away along with its database, the next query starts with a new one
+pub fn reset_pool() -> Option<Arc<Pool>> {
+    POOL.reset()
+}
+
+pub fn monitor() -> Arc<Monitor> {
+    MONITOR.get()
+}
+
+// Checks a connection out of the shared pool, keeping track of how long that took
+// even when no connection was handed out in time
+fn checkout() -> Result<PooledConnection, DbError> {
+    let start = Instant::now();
+    let connection = pool().get();
+    monitor().record_checkout(start.elapsed());
+
+    connection
+}
+
+// Runs the statement and records how long it took and whether it failed
+fn run(connection: &mut dyn Connection, statement: &Statement, params: &[Value]) -> Result<QueryResult, DbError> {
+    let start = Instant::now();
+    let result = connection.execute(statement, params);
+    monitor().record_query(statement.sql(), start.elapsed(), result.is_err());
+
+    result
+}
+
+// Records a query that failed before it could run, e.g. with the wrong number of values
+fn rejected(sql: &str, err: DbError) -> DbError {
+    monitor().record_failure(sql);
+
+    err
+}
+
+pub fn exec(query: &str, params: &[Value]) -> Result<QueryResult, DbError> {
+    exec_prepared(&Statement::prepare(query).map_err(|err| rejected(query, err))?, params)
+}
+
+pub fn exec_prepared(statement: &Statement, params: &[Value]) -> Result<QueryResult, DbError> {
+    statement.check(params).map_err(|err| rejected(statement.sql(), err))?;
+    let mut connection = checkout().map_err(|err| rejected(statement.sql(), err))?;
+
+    run(&mut *connection, statement, params)
+}
+
+pub fn begin() -> Result<Transaction, DbError> {
+    Transaction::begin(checkout()?)
+}
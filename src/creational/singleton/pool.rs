@@ -0,0 +1,265 @@
+// Pool keeps a limited number of connections open and lends them out.
+//
+// A caller checks a connection out, uses it, and the guard puts it back into the pool once it
+// goes out of scope. When every connection is lent out and the pool can't open another one,
+// the caller waits until one is returned or the checkout timeout runs out. Connections that
+// have been sitting idle for too long are closed, and connections that fail their health check
+// are thrown away instead of being handed out. A transaction still open on a returned connection
+// is rolled back.
+//
+// Cloning a pool gives another handle to the same connections, and a lent out connection
+// keeps the pool alive, so it can outlive the handle it was checked out from.
+
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use super::{Backend, Connection, DbError};
+
+#[derive(Clone, Debug)]
+pub struct PoolConfig {
+    pub max_size: usize,
+    pub checkout_timeout: Duration,
+    pub idle_timeout: Duration,
+    // Ping a connection before handing it out
+    pub health_check: bool,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        PoolConfig {
+            max_size: 4,
+            checkout_timeout: Duration::from_secs(5),
+            idle_timeout: Duration::from_secs(60),
+            health_check: true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolStatus {
+    pub open: usize,
+    pub idle: usize,
+    pub max_size: usize,
+}
+
+struct IdleConnection {
+    connection: Box<dyn Connection>,
+    since: Instant,
+}
+
+struct PoolState {
+    idle: Vec<IdleConnection>,
+    // Connections that are either idle or lent out
+    open: usize,
+}
+
+struct Shared {
+    backend: Box<dyn Backend>,
+    config: PoolConfig,
+    state: Mutex<PoolState>,
+    returned: Condvar,
+}
+
+impl Shared {
+    fn evict(&self, state: &mut PoolState) {
+        let before = state.idle.len();
+        state.idle.retain(|idle| idle.since.elapsed() < self.config.idle_timeout);
+        state.open -= before - state.idle.len();
+    }
+
+    fn put_back(&self, mut connection: Box<dyn Connection>) {
+        // A transaction left open must not leak into the next borrower's queries
+        let _ = connection.rollback();
+
+        let mut state = self.state.lock().unwrap();
+        state.idle.push(IdleConnection { connection, since: Instant::now() });
+        self.returned.notify_one();
+    }
+}
+
+#[derive(Clone)]
+pub struct Pool {
+    shared: Arc<Shared>,
+}
+
+#[allow(dead_code)]
+impl Pool {
+    pub fn new(backend: Box<dyn Backend>, config: PoolConfig) -> Pool {
+        let state = Mutex::new(PoolState { idle: vec![], open: 0 });
+
+        Pool { shared: Arc::new(Shared { backend, config, state, returned: Condvar::new() }) }
+    }
+
+    pub fn config(&self) -> &PoolConfig {
+        &self.shared.config
+    }
+
+    pub fn status(&self) -> PoolStatus {
+        let state = self.shared.state.lock().unwrap();
+
+        PoolStatus { open: state.open, idle: state.idle.len(), max_size: self.shared.config.max_size }
+    }
+
+    // Closes the connections that have been idle for longer than the idle timeout
+    pub fn evict_idle(&self) {
+        let mut state = self.shared.state.lock().unwrap();
+        self.shared.evict(&mut state);
+    }
+
+    pub fn get(&self) -> Result<PooledConnection, DbError> {
+        let shared = &self.shared;
+        let deadline = Instant::now() + shared.config.checkout_timeout;
+        let mut state = shared.state.lock().unwrap();
+
+        loop {
+            shared.evict(&mut state);
+
+            if let Some(idle) = state.idle.pop() {
+                let mut connection = idle.connection;
+                if shared.config.health_check && !connection.ping() {
+                    state.open -= 1;
+
+                    continue;
+                }
+
+                return Ok(PooledConnection { pool: Arc::clone(shared), connection: Some(connection) });
+            }
+
+            if state.open < shared.config.max_size {
+                // Reserve the slot so the lock doesn't have to be held while connecting
+                state.open += 1;
+                drop(state);
+
+                return match shared.backend.connect() {
+                    Ok(connection) => Ok(PooledConnection { pool: Arc::clone(shared), connection: Some(connection) }),
+                    Err(err) => {
+                        shared.state.lock().unwrap().open -= 1;
+                        shared.returned.notify_one();
+
+                        Err(err)
+                    },
+                };
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(DbError::Timeout(shared.config.checkout_timeout));
+            }
+
+            state = shared.returned.wait_timeout(state, deadline - now).unwrap().0;
+        }
+    }
+}
+
+// PooledConnection is a connection lent out by the pool. It goes back to the pool when dropped.
+pub struct PooledConnection {
+    pool: Arc<Shared>,
+    connection: Option<Box<dyn Connection>>,
+}
+
+impl Deref for PooledConnection {
+    type Target = dyn Connection;
+
+    fn deref(&self) -> &Self::Target {
+        self.connection.as_deref().unwrap()
+    }
+}
+
+impl DerefMut for PooledConnection {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.connection.as_deref_mut().unwrap()
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        if let Some(connection) = self.connection.take() {
+            self.pool.put_back(connection);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::super::{MemoryBackend, Statement};
+    use super::*;
+
+    fn pool(backend: &MemoryBackend, config: PoolConfig) -> Pool {
+        Pool::new(Box::new(backend.clone()), config)
+    }
+
+    #[test]
+    fn times_out_when_every_connection_is_lent_out() {
+        let config = PoolConfig { max_size: 1, checkout_timeout: Duration::from_millis(20), ..PoolConfig::default() };
+        let pool = pool(&MemoryBackend::new(), config);
+
+        let _held = pool.get().unwrap();
+
+        assert!(matches!(pool.get(), Err(DbError::Timeout(_))));
+    }
+
+    #[test]
+    fn a_waiting_caller_gets_the_returned_connection() {
+        let backend = MemoryBackend::new();
+        let config = PoolConfig { max_size: 1, checkout_timeout: Duration::from_secs(5), ..PoolConfig::default() };
+        let pool = pool(&backend, config);
+
+        let held = pool.get().unwrap();
+        let returning = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            drop(held);
+        });
+
+        assert!(pool.get().is_ok());
+        returning.join().unwrap();
+        assert_eq!(backend.opened(), 1);
+    }
+
+    #[test]
+    fn closes_connections_that_stayed_idle_too_long() {
+        let config = PoolConfig { idle_timeout: Duration::from_millis(1), ..PoolConfig::default() };
+        let pool = pool(&MemoryBackend::new(), config);
+
+        drop(pool.get().unwrap());
+        assert_eq!(pool.status(), PoolStatus { open: 1, idle: 1, max_size: 4 });
+
+        thread::sleep(Duration::from_millis(5));
+        pool.evict_idle();
+        assert_eq!(pool.status(), PoolStatus { open: 0, idle: 0, max_size: 4 });
+    }
+
+    #[test]
+    fn replaces_connections_that_fail_the_health_check() {
+        let backend = MemoryBackend::new();
+        let pool = pool(&backend, PoolConfig::default());
+
+        drop(pool.get().unwrap());
+        backend.break_connections();
+
+        let mut connection = pool.get().unwrap();
+        assert!(connection.ping());
+        assert_eq!(backend.opened(), 2);
+        assert_eq!(pool.status().open, 1);
+    }
+
+    #[test]
+    fn rolls_back_a_transaction_left_open() {
+        let backend = MemoryBackend::new();
+        let pool = pool(&backend, PoolConfig { max_size: 1, ..PoolConfig::default() });
+        let create = Statement::prepare("CREATE TABLE users (name TEXT)").unwrap();
+        let insert = Statement::prepare("INSERT INTO users VALUES ('John')").unwrap();
+
+        let mut connection = pool.get().unwrap();
+        connection.execute(&create, &[]).unwrap();
+        connection.begin().unwrap();
+        connection.execute(&insert, &[]).unwrap();
+        drop(connection);
+
+        let mut connection = pool.get().unwrap();
+        assert!(connection.begin().is_ok());
+        assert!(backend.snapshot().table("users").unwrap().rows.is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+// Queries are sent with their values bound separately instead of pasted into the SQL,
+// so a name like O'Brien can't break out of its string. A `?` in the SQL marks where
+// each value goes, in order.
+//
+// Whatever a query returns comes back as rows of values rather than printed text.
+
+use std::fmt;
+
+use super::DbError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "NULL"),
+            Value::Int(value) => write!(f, "{}", value),
+            Value::Float(value) => write!(f, "{}", value),
+            Value::Text(value) => write!(f, "'{}'", value.replace('\'', "''")),
+        }
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Int(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Float(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Text(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Text(value)
+    }
+}
+
+// Statement is a query that has been checked once and can be executed many times
+#[derive(Clone, Debug)]
+pub struct Statement {
+    sql: String,
+    placeholders: usize,
+}
+
+#[allow(dead_code)]
+impl Statement {
+    pub fn prepare(sql: &str) -> Result<Statement, DbError> {
+        let mut placeholders = 0;
+        let mut quoted = false;
+
+        for c in sql.chars() {
+            match c {
+                '\'' => quoted = !quoted,
+                '?' if !quoted => placeholders += 1,
+                _ => {},
+            }
+        }
+
+        if quoted {
+            return Err(DbError::Syntax(format!("unterminated string in {}", sql)));
+        }
+
+        Ok(Statement { sql: sql.trim().to_string(), placeholders })
+    }
+
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    pub fn placeholders(&self) -> usize {
+        self.placeholders
+    }
+
+    pub fn check(&self, params: &[Value]) -> Result<(), DbError> {
+        if params.len() != self.placeholders {
+            return Err(DbError::ParameterCount { expected: self.placeholders, given: params.len() });
+        }
+
+        Ok(())
+    }
+}
+
+pub type Row = Vec<Value>;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Row>,
+    // Number of rows inserted, updated or deleted
+    pub affected: usize,
+}
+
+#[allow(dead_code)]
+impl QueryResult {
+    pub fn get(&self, row: usize, column: &str) -> Option<&Value> {
+        let index = self.columns.iter().position(|name| name == column)?;
+
+        self.rows.get(row)?.get(index)
+    }
+}
@@ -0,0 +1,200 @@
+// Instead of writing a lazy_static for every global, any type can be kept as a singleton.
+//
+// `Singleton<T>` is a global slot that creates its value the first time it's asked for.
+// The value can be swapped for another one, a fake in a test for example, and a reset
+// empties the slot so the next caller gets a brand new value without restarting the process.
+//
+// `Registry` keeps one value of every type that is registered in it, so services that
+// don't deserve a static of their own can still be fetched from anywhere by their type.
+//
+// Callers get an `Arc` of the value, so a value that has been replaced or reset stays alive
+// until the last caller that still uses it lets it go.
+
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, OnceLock, RwLock};
+
+pub struct Singleton<T> {
+    instance: OnceLock<RwLock<Option<Arc<T>>>>,
+    init: fn() -> T,
+}
+
+#[allow(dead_code)]
+impl<T> Singleton<T> {
+    pub const fn new(init: fn() -> T) -> Singleton<T> {
+        Singleton { instance: OnceLock::new(), init }
+    }
+
+    fn slot(&self) -> &RwLock<Option<Arc<T>>> {
+        self.instance.get_or_init(|| RwLock::new(None))
+    }
+
+    pub fn get(&self) -> Arc<T> {
+        if let Some(instance) = self.slot().read().unwrap().as_ref() {
+            return Arc::clone(instance);
+        }
+
+        // Someone else may have created it while the lock was released
+        let mut slot = self.slot().write().unwrap();
+
+        Arc::clone(slot.get_or_insert_with(|| Arc::new((self.init)())))
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.slot().read().unwrap().is_some()
+    }
+
+    // Puts another value in place of the current one, returning the old one if there was any
+    pub fn replace(&self, instance: T) -> Option<Arc<T>> {
+        self.slot().write().unwrap().replace(Arc::new(instance))
+    }
+
+    // Empties the slot, the next call to `get` creates a new value
+    pub fn reset(&self) -> Option<Arc<T>> {
+        self.slot().write().unwrap().take()
+    }
+}
+
+#[derive(Debug)]
+pub enum RegistryError {
+    AlreadyRegistered(&'static str),
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegistryError::AlreadyRegistered(name) => write!(f, "{} is already registered", name),
+        }
+    }
+}
+
+impl Error for RegistryError {}
+
+type Service = Arc<dyn Any + Send + Sync>;
+
+#[derive(Default)]
+pub struct Registry {
+    services: RwLock<HashMap<TypeId, Service>>,
+}
+
+#[allow(dead_code)]
+impl Registry {
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    // A type can only be registered once, use `replace` to swap it out on purpose
+    pub fn register<T: Any + Send + Sync>(&self, service: T) -> Result<(), RegistryError> {
+        let mut services = self.services.write().unwrap();
+        if services.contains_key(&TypeId::of::<T>()) {
+            return Err(RegistryError::AlreadyRegistered(type_name::<T>()));
+        }
+
+        services.insert(TypeId::of::<T>(), Arc::new(service));
+
+        Ok(())
+    }
+
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        let service = self.services.read().unwrap().get(&TypeId::of::<T>()).cloned()?;
+
+        service.downcast().ok()
+    }
+
+    pub fn is_registered<T: Any + Send + Sync>(&self) -> bool {
+        self.services.read().unwrap().contains_key(&TypeId::of::<T>())
+    }
+
+    pub fn replace<T: Any + Send + Sync>(&self, service: T) -> Option<Arc<T>> {
+        let old = self.services.write().unwrap().insert(TypeId::of::<T>(), Arc::new(service))?;
+
+        old.downcast().ok()
+    }
+
+    pub fn remove<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        let old = self.services.write().unwrap().remove(&TypeId::of::<T>())?;
+
+        old.downcast().ok()
+    }
+
+    // Forgets every service, so the next test starts from scratch
+    pub fn reset(&self) {
+        self.services.write().unwrap().clear();
+    }
+}
+
+static SERVICES: OnceLock<Registry> = OnceLock::new();
+
+// The registry shared by the whole application
+pub fn services() -> &'static Registry {
+    SERVICES.get_or_init(Registry::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn creates_the_value_once() {
+        static CREATED: AtomicUsize = AtomicUsize::new(0);
+        static COUNTER: Singleton<usize> = Singleton::new(|| CREATED.fetch_add(1, Ordering::SeqCst));
+
+        assert!(!COUNTER.is_initialized());
+        assert!(Arc::ptr_eq(&COUNTER.get(), &COUNTER.get()));
+        assert_eq!(CREATED.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn replace_swaps_the_value_for_later_callers() {
+        static NAME: Singleton<String> = Singleton::new(|| "real".to_string());
+
+        let before = NAME.get();
+        let old = NAME.replace("fake".to_string());
+
+        assert!(old.is_some_and(|old| Arc::ptr_eq(&old, &before)));
+        assert_eq!(*before, "real");
+        assert_eq!(*NAME.get(), "fake");
+    }
+
+    #[test]
+    fn reset_creates_a_new_value_on_next_use() {
+        static CREATED: AtomicUsize = AtomicUsize::new(0);
+        static COUNTER: Singleton<usize> = Singleton::new(|| CREATED.fetch_add(1, Ordering::SeqCst));
+
+        assert!(COUNTER.reset().is_none());
+        assert_eq!(*COUNTER.get(), 0);
+        assert_eq!(COUNTER.reset().as_deref(), Some(&0));
+        assert!(!COUNTER.is_initialized());
+        assert_eq!(*COUNTER.get(), 1);
+    }
+
+    #[test]
+    fn registers_a_type_only_once() {
+        let registry = Registry::new();
+
+        registry.register(4usize).unwrap();
+        assert!(matches!(registry.register(8usize), Err(RegistryError::AlreadyRegistered(_))));
+        assert_eq!(registry.get::<usize>().as_deref(), Some(&4));
+        assert!(registry.get::<String>().is_none());
+
+        assert_eq!(registry.replace(8usize).as_deref(), Some(&4));
+        assert_eq!(registry.remove::<usize>().as_deref(), Some(&8));
+        assert!(!registry.is_registered::<usize>());
+    }
+
+    #[test]
+    fn reset_forgets_every_service() {
+        let registry = Registry::new();
+        registry.register(4usize).unwrap();
+        registry.register("config".to_string()).unwrap();
+
+        registry.reset();
+
+        assert!(!registry.is_registered::<usize>());
+        assert!(!registry.is_registered::<String>());
+    }
+}
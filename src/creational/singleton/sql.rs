@@ -0,0 +1,479 @@
+// A small subset of SQL, just enough to test services without a real server:
+//
+//   CREATE TABLE users (name TEXT, age INTEGER)
+//   INSERT INTO users (name, age) VALUES ('John Doe', 123), (?, ?)
+//   SELECT name, age FROM users WHERE age >= 18 AND name != 'Bob'
+//   UPDATE users SET age = ? WHERE name = ?
+//   DELETE FROM users WHERE age < 18 OR age IS NULL
+//
+// Keywords are case-insensitive. The bound values take the place of the `?` as the query
+// is parsed, so they never go through the tokenizer.
+
+use super::{DbError, Value};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnType {
+    Integer,
+    Real,
+    Text,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Condition {
+    Compare { column: String, operator: Operator, value: Value },
+    IsNull { column: String, negated: bool },
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    CreateTable { table: String, columns: Vec<(String, ColumnType)> },
+    Insert { table: String, columns: Option<Vec<String>>, rows: Vec<Vec<Value>> },
+    // No columns means every column, as in `SELECT *`
+    Select { table: String, columns: Vec<String>, filter: Option<Condition> },
+    Update { table: String, assignments: Vec<(String, Value)>, filter: Option<Condition> },
+    Delete { table: String, filter: Option<Condition> },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Symbol(&'static str),
+    Placeholder,
+}
+
+fn tokenize(sql: &str) -> Result<Vec<Token>, DbError> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Word(chars[start..i].iter().collect()));
+        } else if c.is_ascii_digit() || (c == '-' && chars.get(i + 1).is_some_and(|next| next.is_ascii_digit())) {
+            let start = i;
+            i += 1;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+
+            let number: String = chars[start..i].iter().collect();
+            let token = if number.contains('.') {
+                number.parse().map(Token::Float).ok()
+            } else {
+                number.parse().map(Token::Int).ok()
+            };
+            tokens.push(token.ok_or_else(|| DbError::Syntax(format!("{} is not a number", number)))?);
+        } else if c == '\'' {
+            // Two quotes in a row stand for a quote inside the string
+            let mut text = String::new();
+            i += 1;
+            loop {
+                match (chars.get(i), chars.get(i + 1)) {
+                    (Some('\''), Some('\'')) => {
+                        text.push('\'');
+                        i += 2;
+                    },
+                    (Some('\''), _) => {
+                        i += 1;
+                        break;
+                    },
+                    (Some(c), _) => {
+                        text.push(*c);
+                        i += 1;
+                    },
+                    (None, _) => return Err(DbError::Syntax("unterminated string".to_string())),
+                }
+            }
+            tokens.push(Token::Text(text));
+        } else if c == '?' {
+            tokens.push(Token::Placeholder);
+            i += 1;
+        } else {
+            let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+            let symbol = ["!=", "<>", "<=", ">="]
+                .into_iter()
+                .find(|symbol| *symbol == two)
+                .or_else(|| ["(", ")", ",", "*", "=", "<", ">", ";"].into_iter().find(|symbol| symbol.starts_with(c)))
+                .ok_or_else(|| DbError::Syntax(format!("unexpected character {}", c)))?;
+
+            tokens.push(Token::Symbol(symbol));
+            i += symbol.len();
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    position: usize,
+    params: std::slice::Iter<'a, Value>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+
+        token
+    }
+
+    fn error(&self, expected: &str) -> DbError {
+        match self.tokens.get(self.position) {
+            Some(token) => DbError::Syntax(format!("expected {} but found {:?}", expected, token)),
+            None => DbError::Syntax(format!("expected {} but the query ended", expected)),
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn accept_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        if found {
+            self.position += 1;
+        }
+
+        found
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Result<(), DbError> {
+        if !self.accept_keyword(keyword) {
+            return Err(self.error(keyword));
+        }
+
+        Ok(())
+    }
+
+    fn accept_symbol(&mut self, symbol: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Symbol(found)) if *found == symbol);
+        if found {
+            self.position += 1;
+        }
+
+        found
+    }
+
+    fn symbol(&mut self, symbol: &str) -> Result<(), DbError> {
+        if !self.accept_symbol(symbol) {
+            return Err(self.error(symbol));
+        }
+
+        Ok(())
+    }
+
+    fn identifier(&mut self) -> Result<String, DbError> {
+        match self.next() {
+            Some(Token::Word(word)) => Ok(word),
+            _ => {
+                self.position -= 1;
+
+                Err(self.error("a name"))
+            },
+        }
+    }
+
+    // Parses a comma separated list, the opening parenthesis has to be consumed already
+    fn list<T, F>(&mut self, mut item: F) -> Result<Vec<T>, DbError>
+    where
+        F: FnMut(&mut Self) -> Result<T, DbError>,
+    {
+        let mut items = vec![item(self)?];
+        while self.accept_symbol(",") {
+            items.push(item(self)?);
+        }
+        self.symbol(")")?;
+
+        Ok(items)
+    }
+
+    fn value(&mut self) -> Result<Value, DbError> {
+        match self.next() {
+            Some(Token::Int(value)) => Ok(Value::Int(value)),
+            Some(Token::Float(value)) => Ok(Value::Float(value)),
+            Some(Token::Text(value)) => Ok(Value::Text(value)),
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("NULL") => Ok(Value::Null),
+            Some(Token::Placeholder) => self.params
+                .next()
+                .cloned()
+                .ok_or_else(|| DbError::Syntax("not enough values for the placeholders".to_string())),
+            _ => {
+                self.position -= 1;
+
+                Err(self.error("a value"))
+            },
+        }
+    }
+
+    fn column_type(&mut self) -> Result<ColumnType, DbError> {
+        let name = self.identifier()?;
+
+        match name.to_uppercase().as_str() {
+            "INT" | "INTEGER" => Ok(ColumnType::Integer),
+            "REAL" | "FLOAT" => Ok(ColumnType::Real),
+            "TEXT" | "VARCHAR" => Ok(ColumnType::Text),
+            _ => Err(DbError::Syntax(format!("unknown column type {}", name))),
+        }
+    }
+
+    // OR binds looser than AND
+    fn condition(&mut self) -> Result<Condition, DbError> {
+        let mut condition = self.conjunction()?;
+        while self.accept_keyword("OR") {
+            condition = Condition::Or(Box::new(condition), Box::new(self.conjunction()?));
+        }
+
+        Ok(condition)
+    }
+
+    fn conjunction(&mut self) -> Result<Condition, DbError> {
+        let mut condition = self.comparison()?;
+        while self.accept_keyword("AND") {
+            condition = Condition::And(Box::new(condition), Box::new(self.comparison()?));
+        }
+
+        Ok(condition)
+    }
+
+    fn comparison(&mut self) -> Result<Condition, DbError> {
+        if self.accept_symbol("(") {
+            let condition = self.condition()?;
+            self.symbol(")")?;
+
+            return Ok(condition);
+        }
+
+        let column = self.identifier()?;
+        if self.accept_keyword("IS") {
+            let negated = self.accept_keyword("NOT");
+            self.keyword("NULL")?;
+
+            return Ok(Condition::IsNull { column, negated });
+        }
+
+        let operator = match self.next() {
+            Some(Token::Symbol("=")) => Operator::Eq,
+            Some(Token::Symbol("!=")) | Some(Token::Symbol("<>")) => Operator::NotEq,
+            Some(Token::Symbol("<")) => Operator::Lt,
+            Some(Token::Symbol("<=")) => Operator::LtEq,
+            Some(Token::Symbol(">")) => Operator::Gt,
+            Some(Token::Symbol(">=")) => Operator::GtEq,
+            _ => {
+                self.position -= 1;
+
+                return Err(self.error("a comparison"));
+            },
+        };
+
+        Ok(Condition::Compare { column, operator, value: self.value()? })
+    }
+
+    fn filter(&mut self) -> Result<Option<Condition>, DbError> {
+        if !self.accept_keyword("WHERE") {
+            return Ok(None);
+        }
+
+        Ok(Some(self.condition()?))
+    }
+
+    fn command(&mut self) -> Result<Command, DbError> {
+        if self.accept_keyword("CREATE") {
+            self.keyword("TABLE")?;
+            let table = self.identifier()?;
+            self.symbol("(")?;
+            let columns = self.list(|parser| Ok((parser.identifier()?, parser.column_type()?)))?;
+
+            return Ok(Command::CreateTable { table, columns });
+        }
+
+        if self.accept_keyword("INSERT") {
+            self.keyword("INTO")?;
+            let table = self.identifier()?;
+            let columns = if self.accept_symbol("(") { Some(self.list(Parser::identifier)?) } else { None };
+            self.keyword("VALUES")?;
+
+            let mut rows = vec![];
+            loop {
+                self.symbol("(")?;
+                rows.push(self.list(Parser::value)?);
+                if !self.accept_symbol(",") {
+                    break;
+                }
+            }
+
+            return Ok(Command::Insert { table, columns, rows });
+        }
+
+        if self.accept_keyword("SELECT") {
+            let columns = if self.accept_symbol("*") {
+                vec![]
+            } else {
+                let mut columns = vec![self.identifier()?];
+                while self.accept_symbol(",") {
+                    columns.push(self.identifier()?);
+                }
+
+                columns
+            };
+            self.keyword("FROM")?;
+            let table = self.identifier()?;
+
+            return Ok(Command::Select { table, columns, filter: self.filter()? });
+        }
+
+        if self.accept_keyword("UPDATE") {
+            let table = self.identifier()?;
+            self.keyword("SET")?;
+
+            let mut assignments = vec![];
+            loop {
+                let column = self.identifier()?;
+                self.symbol("=")?;
+                assignments.push((column, self.value()?));
+                if !self.accept_symbol(",") {
+                    break;
+                }
+            }
+
+            return Ok(Command::Update { table, assignments, filter: self.filter()? });
+        }
+
+        if self.accept_keyword("DELETE") {
+            self.keyword("FROM")?;
+            let table = self.identifier()?;
+
+            return Ok(Command::Delete { table, filter: self.filter()? });
+        }
+
+        Err(self.error("CREATE, INSERT, SELECT, UPDATE or DELETE"))
+    }
+}
+
+pub fn parse(sql: &str, params: &[Value]) -> Result<Command, DbError> {
+    let mut parser = Parser { tokens: tokenize(sql)?, position: 0, params: params.iter() };
+    let command = parser.command()?;
+
+    parser.accept_symbol(";");
+    if parser.peek().is_some() {
+        return Err(parser.error("the end of the query"));
+    }
+
+    Ok(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenizes_strings_numbers_and_symbols() {
+        let tokens = tokenize("SELECT * FROM t WHERE a >= -1.5 AND b <> 'O''Brien';").unwrap();
+
+        assert_eq!(tokens, vec![
+            Token::Word("SELECT".to_string()),
+            Token::Symbol("*"),
+            Token::Word("FROM".to_string()),
+            Token::Word("t".to_string()),
+            Token::Word("WHERE".to_string()),
+            Token::Word("a".to_string()),
+            Token::Symbol(">="),
+            Token::Float(-1.5),
+            Token::Word("AND".to_string()),
+            Token::Word("b".to_string()),
+            Token::Symbol("<>"),
+            Token::Text("O'Brien".to_string()),
+            Token::Symbol(";"),
+        ]);
+    }
+
+    #[test]
+    fn rejects_unterminated_strings_and_unknown_characters() {
+        assert!(matches!(tokenize("SELECT 'oops"), Err(DbError::Syntax(_))));
+        assert!(matches!(tokenize("SELECT # FROM t"), Err(DbError::Syntax(_))));
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        let command = parse("select * from t where a = 1 or b = 2 and c is not null", &[]).unwrap();
+
+        let compare = |column: &str, value: i64| Condition::Compare {
+            column: column.to_string(),
+            operator: Operator::Eq,
+            value: Value::Int(value),
+        };
+        let expected = Condition::Or(
+            Box::new(compare("a", 1)),
+            Box::new(Condition::And(
+                Box::new(compare("b", 2)),
+                Box::new(Condition::IsNull { column: "c".to_string(), negated: true }),
+            )),
+        );
+
+        assert_eq!(command, Command::Select { table: "t".to_string(), columns: vec![], filter: Some(expected) });
+    }
+
+    #[test]
+    fn binds_values_in_place_of_placeholders() {
+        let params = [Value::from("O'Brien"), Value::Null];
+        let command = parse("INSERT INTO users (name, age) VALUES (?, ?), ('Bob', 42)", &params).unwrap();
+
+        assert_eq!(command, Command::Insert {
+            table: "users".to_string(),
+            columns: Some(vec!["name".to_string(), "age".to_string()]),
+            rows: vec![
+                vec![Value::from("O'Brien"), Value::Null],
+                vec![Value::from("Bob"), Value::Int(42)],
+            ],
+        });
+    }
+
+    #[test]
+    fn rejects_missing_values_and_trailing_tokens() {
+        assert!(matches!(parse("UPDATE t SET a = ? WHERE b = ?", &[Value::Int(1)]), Err(DbError::Syntax(_))));
+        assert!(matches!(parse("DELETE FROM t WHERE a = 1 2", &[]), Err(DbError::Syntax(_))));
+        assert!(matches!(parse("DROP TABLE t", &[]), Err(DbError::Syntax(_))));
+    }
+
+    #[test]
+    fn parses_column_types() {
+        let command = parse("CREATE TABLE t (a INT, b real, c VARCHAR)", &[]).unwrap();
+
+        assert_eq!(command, Command::CreateTable {
+            table: "t".to_string(),
+            columns: vec![
+                ("a".to_string(), ColumnType::Integer),
+                ("b".to_string(), ColumnType::Real),
+                ("c".to_string(), ColumnType::Text),
+            ],
+        });
+        assert!(matches!(parse("CREATE TABLE t (a BLOB)", &[]), Err(DbError::Syntax(_))));
+    }
+}
@@ -0,0 +1,270 @@
+// Store keeps the tables of the in-memory database and runs parsed commands against them.
+//
+// Values have to fit the type of their column, although an integer is welcome in a REAL column.
+// Any column can be NULL. Comparisons with NULL are never true, use IS NULL instead.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use super::sql::{ColumnType, Command, Condition, Operator};
+use super::{DbError, QueryResult, Row, Value};
+
+#[derive(Clone, Debug)]
+pub struct Table {
+    pub columns: Vec<(String, ColumnType)>,
+    pub rows: Vec<Row>,
+}
+
+impl Table {
+    fn column(&self, name: &str) -> Result<usize, DbError> {
+        self.columns
+            .iter()
+            .position(|(column, _)| column.eq_ignore_ascii_case(name))
+            .ok_or_else(|| DbError::UnknownColumn(name.to_string()))
+    }
+
+    // Checks the value against the type of the column, converting it if needed
+    fn fit(&self, index: usize, value: Value) -> Result<Value, DbError> {
+        let (name, column_type) = &self.columns[index];
+
+        match (column_type, value) {
+            (_, Value::Null) => Ok(Value::Null),
+            (ColumnType::Integer, Value::Int(value)) => Ok(Value::Int(value)),
+            (ColumnType::Real, Value::Int(value)) => Ok(Value::Float(value as f64)),
+            (ColumnType::Real, Value::Float(value)) => Ok(Value::Float(value)),
+            (ColumnType::Text, Value::Text(value)) => Ok(Value::Text(value)),
+            (_, value) => Err(DbError::TypeMismatch { column: name.clone(), value }),
+        }
+    }
+
+    fn matches(&self, row: &Row, condition: &Condition) -> Result<bool, DbError> {
+        match condition {
+            Condition::Compare { column, operator, value } => {
+                let ordering = compare(&row[self.column(column)?], value);
+
+                Ok(ordering.is_some_and(|ordering| match operator {
+                    Operator::Eq => ordering == Ordering::Equal,
+                    Operator::NotEq => ordering != Ordering::Equal,
+                    Operator::Lt => ordering == Ordering::Less,
+                    Operator::LtEq => ordering != Ordering::Greater,
+                    Operator::Gt => ordering == Ordering::Greater,
+                    Operator::GtEq => ordering != Ordering::Less,
+                }))
+            },
+            Condition::IsNull { column, negated } => Ok((row[self.column(column)?] == Value::Null) != *negated),
+            Condition::And(left, right) => Ok(self.matches(row, left)? && self.matches(row, right)?),
+            Condition::Or(left, right) => Ok(self.matches(row, left)? || self.matches(row, right)?),
+        }
+    }
+
+    fn filter(&self, filter: &Option<Condition>) -> Result<Vec<usize>, DbError> {
+        let mut indices = vec![];
+        for (index, row) in self.rows.iter().enumerate() {
+            if filter.as_ref().map_or(Ok(true), |condition| self.matches(row, condition))? {
+                indices.push(index);
+            }
+        }
+
+        Ok(indices)
+    }
+}
+
+// Numbers compare with numbers and text with text, anything else doesn't compare at all
+fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Int(left), Value::Int(right)) => Some(left.cmp(right)),
+        (Value::Int(left), Value::Float(right)) => (*left as f64).partial_cmp(right),
+        (Value::Float(left), Value::Int(right)) => left.partial_cmp(&(*right as f64)),
+        (Value::Float(left), Value::Float(right)) => left.partial_cmp(right),
+        (Value::Text(left), Value::Text(right)) => Some(left.cmp(right)),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Store {
+    tables: BTreeMap<String, Table>,
+}
+
+#[allow(dead_code)]
+impl Store {
+    pub fn table_names(&self) -> Vec<&str> {
+        self.tables.keys().map(|name| name.as_str()).collect()
+    }
+
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.get(&name.to_lowercase())
+    }
+
+    fn table_mut(&mut self, name: &str) -> Result<&mut Table, DbError> {
+        self.tables.get_mut(&name.to_lowercase()).ok_or_else(|| DbError::UnknownTable(name.to_string()))
+    }
+
+    pub fn execute(&mut self, command: Command) -> Result<QueryResult, DbError> {
+        match command {
+            Command::CreateTable { table, columns } => {
+                if self.tables.contains_key(&table.to_lowercase()) {
+                    return Err(DbError::TableExists(table));
+                }
+
+                self.tables.insert(table.to_lowercase(), Table { columns, rows: vec![] });
+
+                Ok(QueryResult::default())
+            },
+            Command::Insert { table, columns, rows } => {
+                let table = self.table_mut(&table)?;
+                let indices = match columns {
+                    Some(columns) => columns.iter().map(|column| table.column(column)).collect::<Result<Vec<_>, _>>()?,
+                    None => (0..table.columns.len()).collect(),
+                };
+
+                // Every row is checked before any of them is inserted
+                let mut inserted = vec![];
+                for values in rows {
+                    if values.len() != indices.len() {
+                        return Err(DbError::Syntax(format!("expected {} values but found {}", indices.len(), values.len())));
+                    }
+
+                    let mut row = vec![Value::Null; table.columns.len()];
+                    for (index, value) in indices.iter().zip(values) {
+                        row[*index] = table.fit(*index, value)?;
+                    }
+                    inserted.push(row);
+                }
+
+                let affected = inserted.len();
+                table.rows.extend(inserted);
+
+                Ok(QueryResult { affected, ..QueryResult::default() })
+            },
+            Command::Select { table, columns, filter } => {
+                let table = self.table_mut(&table)?;
+                let indices = if columns.is_empty() {
+                    (0..table.columns.len()).collect()
+                } else {
+                    columns.iter().map(|column| table.column(column)).collect::<Result<Vec<_>, _>>()?
+                };
+
+                let rows = table
+                    .filter(&filter)?
+                    .into_iter()
+                    .map(|row| indices.iter().map(|index| table.rows[row][*index].clone()).collect())
+                    .collect();
+
+                Ok(QueryResult {
+                    columns: indices.iter().map(|index| table.columns[*index].0.clone()).collect(),
+                    rows,
+                    affected: 0,
+                })
+            },
+            Command::Update { table, assignments, filter } => {
+                let table = self.table_mut(&table)?;
+                let mut changes = vec![];
+                for (column, value) in assignments {
+                    let index = table.column(&column)?;
+                    changes.push((index, table.fit(index, value)?));
+                }
+
+                let rows = table.filter(&filter)?;
+                for row in rows.iter() {
+                    for (index, value) in changes.iter() {
+                        table.rows[*row][*index] = value.clone();
+                    }
+                }
+
+                Ok(QueryResult { affected: rows.len(), ..QueryResult::default() })
+            },
+            Command::Delete { table, filter } => {
+                let table = self.table_mut(&table)?;
+                let rows = table.filter(&filter)?;
+
+                let mut index = 0;
+                table.rows.retain(|_| {
+                    let keep = !rows.contains(&index);
+                    index += 1;
+
+                    keep
+                });
+
+                Ok(QueryResult { affected: rows.len(), ..QueryResult::default() })
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::sql;
+    use super::*;
+
+    fn run(store: &mut Store, query: &str) -> Result<QueryResult, DbError> {
+        store.execute(sql::parse(query, &[])?)
+    }
+
+    fn users() -> Store {
+        let mut store = Store::default();
+        run(&mut store, "CREATE TABLE users (name TEXT, age INTEGER, height REAL)").unwrap();
+        run(&mut store, "INSERT INTO users VALUES ('John', 30, 1.8), ('Jane', NULL, 1.7), ('Bob', 12, NULL)").unwrap();
+
+        store
+    }
+
+    #[test]
+    fn values_must_fit_their_column() {
+        let mut store = users();
+
+        run(&mut store, "INSERT INTO users (name, height) VALUES ('Ann', 2)").unwrap();
+        let result = run(&mut store, "SELECT height FROM users WHERE name = 'Ann'").unwrap();
+        assert_eq!(result.rows, vec![vec![Value::Float(2.0)]]);
+
+        let err = run(&mut store, "INSERT INTO users (name, age) VALUES ('Tim', 'old')");
+        assert!(matches!(err, Err(DbError::TypeMismatch { column, .. }) if column == "age"));
+    }
+
+    #[test]
+    fn a_bad_row_stops_the_whole_insert() {
+        let mut store = users();
+
+        let err = run(&mut store, "INSERT INTO users (name) VALUES ('Tim'), (42)");
+        assert!(matches!(err, Err(DbError::TypeMismatch { .. })));
+        assert_eq!(store.table("users").unwrap().rows.len(), 3);
+    }
+
+    #[test]
+    fn comparisons_with_null_are_never_true() {
+        let mut store = users();
+
+        let adults = run(&mut store, "SELECT name FROM users WHERE age >= 18 OR age < 18").unwrap();
+        assert_eq!(adults.rows, vec![vec![Value::from("John")], vec![Value::from("Bob")]]);
+
+        let unknown = run(&mut store, "SELECT name FROM users WHERE age IS NULL").unwrap();
+        assert_eq!(unknown.rows, vec![vec![Value::from("Jane")]]);
+
+        let none = run(&mut store, "SELECT name FROM users WHERE age = NULL").unwrap();
+        assert!(none.rows.is_empty());
+    }
+
+    #[test]
+    fn updates_and_deletes_report_affected_rows() {
+        let mut store = users();
+
+        let updated = run(&mut store, "UPDATE users SET age = 18 WHERE age IS NULL OR age < 18").unwrap();
+        assert_eq!(updated.affected, 2);
+
+        let deleted = run(&mut store, "DELETE FROM users WHERE age = 18").unwrap();
+        assert_eq!(deleted.affected, 2);
+
+        let result = run(&mut store, "SELECT * FROM users").unwrap();
+        assert_eq!(result.columns, vec!["name", "age", "height"]);
+        assert_eq!(result.rows, vec![vec![Value::from("John"), Value::Int(30), Value::Float(1.8)]]);
+    }
+
+    #[test]
+    fn reports_unknown_tables_and_columns() {
+        let mut store = users();
+
+        assert!(matches!(run(&mut store, "SELECT * FROM cars"), Err(DbError::UnknownTable(_))));
+        assert!(matches!(run(&mut store, "SELECT weight FROM users"), Err(DbError::UnknownColumn(_))));
+        assert!(matches!(run(&mut store, "CREATE TABLE Users (a TEXT)"), Err(DbError::TableExists(_))));
+    }
+}
@@ -0,0 +1,51 @@
+// Transaction holds on to one connection from the pool so every query in it runs on
+// the same connection. Nothing it does is kept unless it's committed, and a transaction
+// that is dropped without being committed is rolled back.
+//
+// Queries in a transaction are recorded by the monitor like any other query.
+
+use super::{rejected, run, DbError, PooledConnection, QueryResult, Statement, Value};
+
+pub struct Transaction {
+    connection: PooledConnection,
+    finished: bool,
+}
+
+#[allow(dead_code)]
+impl Transaction {
+    pub fn begin(mut connection: PooledConnection) -> Result<Transaction, DbError> {
+        connection.begin()?;
+
+        Ok(Transaction { connection, finished: false })
+    }
+
+    pub fn exec(&mut self, query: &str, params: &[Value]) -> Result<QueryResult, DbError> {
+        self.exec_prepared(&Statement::prepare(query).map_err(|err| rejected(query, err))?, params)
+    }
+
+    pub fn exec_prepared(&mut self, statement: &Statement, params: &[Value]) -> Result<QueryResult, DbError> {
+        statement.check(params).map_err(|err| rejected(statement.sql(), err))?;
+
+        run(&mut *self.connection, statement, params)
+    }
+
+    pub fn commit(mut self) -> Result<(), DbError> {
+        self.finished = true;
+
+        self.connection.commit()
+    }
+
+    pub fn rollback(mut self) -> Result<(), DbError> {
+        self.finished = true;
+
+        self.connection.rollback()
+    }
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.connection.rollback();
+        }
+    }
+}
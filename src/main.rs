@@ -1,11 +1,14 @@
+use std::time::Duration;
+
 mod creational;
 mod structural;
 mod behavioral;
 
-use creational::builder::{PCBuilder, Processor};
-use creational::factory::{Cargo, deliver_cargo};
-use creational::abstract_factory::{WindowsUIManager, AppUIManager};
-use creational::singleton::exec;
+use creational::builder::{suggest_build, BuildConstraints, BuildError, Memory, Motherboard, PCBuilder, PartCatalog, PowerSupply, Processor, Storage, TypedPCBuilder};
+use creational::factory::{Cargo, DeliveryPolicy, Tracker, TrackingEvent, TrackingLog, VehicleDealer, deliver_cargo};
+use creational::abstract_factory::{AppUIManager, Event, EventKind, Length, Matches, Numeric, Platform, ThemeRegistry, WindowsUIManager};
+use creational::singleton::{begin, exec, exec_prepared, monitor, pool, replace_pool, reset_pool, services, MemoryBackend, Pool, PoolConfig, Statement, Value};
+use creational::prototype::{self, Prototype, PrototypeRegistry, Team};
 
 use structural::adapter::{EuropeanSocket, LaptopCharger, PowerConverter};
 use structural::bridge::{NextGenerationRemoteControl, RemoteControl, TV};
@@ -30,28 +33,273 @@ fn main() {
 
     // Factory method calls
     let cargos = vec![
-        Cargo{ weight: 123, destination: "Somewhere, Sumatra, Indonesia".to_string() },
-        Cargo{ weight: 456, destination: "Somewhat, West Java, Indonesia".to_string() },
-        Cargo{ weight: 222, destination: "Somehow, East Java, Indonesia".to_string() },
+        Cargo{ weight: 123, destination: "Somewhere, Sumatra, Indonesia".parse().unwrap() },
+        Cargo{ weight: 456, destination: "Somewhat, West Java, Indonesia".parse().unwrap() },
+        Cargo{ weight: 222, destination: "Somehow, East Java, Indonesia".parse().unwrap() },
+        Cargo{ weight: 1500, destination: "Paris, Ile-de-France, France".parse().unwrap() },
+        // Only two trucks in the fleet, so these wait until one of them comes back
+        Cargo{ weight: 300, destination: "Somewhen, Central Java, Indonesia".parse().unwrap() },
+        Cargo{ weight: 700, destination: "Someone, Jakarta, Indonesia".parse().unwrap() },
     ];
 
-    deliver_cargo(cargos);
+    // Dashboards follow the shipments by subscribing to the tracker
+    let tracking_log = TrackingLog::default();
+    let dashboard = |event: &TrackingEvent| println!("[{}] {} is {}", event.tracking_id, event.vehicle, event.status);
+    let mut tracker = Tracker::new();
+    tracker.subscribe(&tracking_log).subscribe(&dashboard);
+
+    let dealer = VehicleDealer::default();
+    let plan = deliver_cargo(&dealer, cargos, DeliveryPolicy::Cheapest, &tracker);
+    for shipment in plan.shipments() {
+        let quote = &shipment.quote;
+        println!("{} to {} ({}km) costs {:.2} and takes {:.1} hours", quote.kind, shipment.cargo.destination, quote.distance, quote.cost, quote.hours);
+
+        if let Some(receipt) = &shipment.receipt {
+            println!("Receipt {}: {} ({} events)", receipt.tracking_id, receipt.status, tracking_log.history(&receipt.tracking_id).len());
+        }
+    }
+
+    // Urgent cargo goes with whichever vehicle arrives first
+    if let Ok(quote) = dealer.quote(&Cargo{ weight: 50, destination: "Somewhere, East Java, Indonesia".parse().unwrap() }, DeliveryPolicy::Fastest) {
+        println!("Urgent cargo goes by {} in {:.1} hours", quote.kind, quote.hours);
+    }
+
+    // Cross-island shipments are split into legs, each with its own vehicle
+    match dealer.plan_route(&"Somewhere, North Sulawesi, Indonesia".parse().unwrap(), 500) {
+        Ok(route) => {
+            route.travel(&dealer, &tracker);
+        },
+        Err(err) => println!("Cannot plan a route: {}", err),
+    }
 
     // Abstract factory pattern
     let ui_manager = AppUIManager{ os_manager: Box::new(WindowsUIManager{}) };
-    let button = ui_manager.create_button();
+    let button = ui_manager.create_button("Say hello");
     button.click();
-    let input = ui_manager.create_input();
+    let mut input = ui_manager.create_input();
+    input.on(EventKind::Change, Box::new(|event| println!("Input changed: {:?}", event)));
     input.on_input("Hello World!".to_string());
 
+    // The kit can also be picked from the platform the app runs on, with an optional theme on top
+    let themes = ThemeRegistry::default();
+    match AppUIManager::detect(&themes) {
+        Ok(ui_manager) => ui_manager.create_button("Say hello").click(),
+        Err(err) => println!("{}", err),
+    }
+    if let Ok(ui_manager) = AppUIManager::with_theme(Platform::Linux, "dark", &themes) {
+        ui_manager.create_input().on_focus();
+    }
+
+    // A whole form comes from the same kit, so every component looks alike
+    let ui_manager = AppUIManager::new(Platform::Mac);
+    let heading = ui_manager.create_label("Shipping preferences");
+    heading.show();
+    let mut express = ui_manager.create_checkbox("Express delivery");
+    express.toggle();
+    let mut carrier = ui_manager.create_dropdown(&["Truck", "Ship", "Plane"]);
+    carrier.select("Ship");
+    let mut insurance = ui_manager.create_slider(0, 100);
+    insurance.slide(150);
+    let confirmation = ui_manager.create_dialog("Confirm order");
+    confirmation.open();
+    println!("{}: express {}, carrier {:?}, insurance {}%", heading.text(), express.is_checked(), carrier.selected(), insurance.value());
+    confirmation.close();
+
+    // Inputs keep their value, check it against validators and report what happens to them
+    let mut postal_code = ui_manager.create_input();
+    postal_code.add_validator(Box::new(Matches::new(r"^\d{5}$").unwrap()));
+    postal_code.add_validator(Box::new(Length{ min: 5, max: 5 }));
+    postal_code.on(EventKind::Change, Box::new(|event| {
+        if let Event::Change(value) = event {
+            println!("Postal code is now {}", value);
+        }
+    }));
+    postal_code.on(EventKind::Blur, Box::new(|_| println!("Postal code lost focus")));
+    postal_code.on(EventKind::Click, Box::new(|_| println!("Postal code was clicked")));
+    postal_code.click();
+    postal_code.on_focus();
+    postal_code.on_input("1234".to_string());
+    postal_code.on_blur();
+    if let Err(errors) = postal_code.validate() {
+        for err in errors {
+            println!("{}: {}", postal_code.value(), err);
+        }
+    }
+
+    let mut quantity = ui_manager.create_input();
+    quantity.add_validator(Box::new(Numeric{ min: 1.0, max: 10.0 }));
+    quantity.on_input("3".to_string());
+    println!("Quantity {} is valid: {}", quantity.value(), quantity.validate().is_ok());
+
+    // Without a display, the form can still be drawn as text, e.g. to compare against a snapshot
+    let mut submit = ui_manager.create_button("Submit");
+    submit.on(EventKind::Click, Box::new(|_| println!("Submitting the order")));
+    submit.on(EventKind::Focus, Box::new(|_| println!("Submit is focused")));
+    submit.on(EventKind::Change, Box::new(|event| println!("Submit button changed: {:?}", event)));
+    submit.on_focus();
+    submit.set_label("Place order");
+    submit.click();
+    submit.on_blur();
+    let cancel = ui_manager.create_button("Cancel");
+    let mut frame = ui_manager.create_frame("Checkout");
+    frame
+        .add(heading.as_ref())
+        .add(express.as_ref())
+        .add(carrier.as_ref())
+        .add(insurance.as_ref())
+        .add(postal_code.as_ref())
+        .add(quantity.as_ref())
+        .add_row(&[submit.as_ref(), cancel.as_ref()]);
+    println!("{}", frame.render());
+
     // Builder pattern
     let mut pc_builder = PCBuilder::new_builder();
-    let processor = Processor{ socket: "AM5".to_string(), series: "7700X".to_string(), cores: 8, threads: 16, tdp: 105 };
-    pc_builder.buy_processor(processor);
+    let processor = Processor{ socket: "AM5".to_string(), series: "7700X".to_string(), cores: 8, threads: 16, tdp: 105, price: 32999 };
+    let _ = pc_builder.buy_processor(processor);
+
+    // A violation names every part involved, so the buyer knows which ones to swap
+    let am4_motherboard = Motherboard{ model: "B550 Tomahawk".to_string(), socket: "AM4".to_string(), memory_type: "DDR4".to_string(), storage_type: vec!["NVMe".to_string(), "SATA".to_string()], pcie: 4, max_memory_per_slot: 32, memory_slots: 4, storage_slots: 6, pcie_slots: 2, price: 17999 };
+    if let Err(BuildError::Incompatible(violations)) = pc_builder.buy_motherboard(am4_motherboard) {
+        for violation in violations {
+            let parts: Vec<String> = violation.parts.iter().map(|part| part.to_string()).collect();
+            println!("{} ({})", violation, parts.join(", "));
+        }
+    }
+
+    // Parts can also be picked from a catalog that only shows the ones that fit
+    if let Ok(catalog) = PartCatalog::load("src/creational/builder/catalog.json") {
+        for motherboard in catalog.compatible_motherboards(&pc_builder) {
+            println!("{} fits the current build", motherboard.model);
+        }
+
+        // Or let the solver pick the best build within the constraints
+        let constraints = BuildConstraints{ budget: Some(150_000), min_cores: 6, min_gpu_memory: 8, storage_interface: Some("NVMe".to_string()) };
+        if let Ok(suggestion) = suggest_build(&catalog, &constraints) {
+            let _suggested_pc = suggestion.build_pc();
+        }
+    }
     let _result = pc_builder.build_pc(); // evaluate here 
 
+    // An unfinished build can be saved and continued later
+    if let Ok(session) = pc_builder.to_json() {
+        if let Ok((_resumed, violations)) = PCBuilder::from_json(&session) {
+            for violation in violations {
+                println!("The saved build no longer fits: {}", violation);
+            }
+        }
+    }
+
+    // Typestate builder, calling `build` before buying all required parts won't compile
+    let typed_pc = TypedPCBuilder::new()
+        .buy_processor(Processor{ socket: "AM5".to_string(), series: "7600".to_string(), cores: 6, threads: 12, tdp: 65, price: 19999 })
+        .buy_motherboard(Motherboard{ model: "X670E Hero".to_string(), socket: "AM5".to_string(), memory_type: "DDR5".to_string(), storage_type: vec!["NVMe".to_string()], pcie: 5, max_memory_per_slot: 48, memory_slots: 4, storage_slots: 4, pcie_slots: 2, price: 62999 })
+        .buy_memory(Memory{ model: "Vengeance DDR5 16GB".to_string(), socket: "DDR5".to_string(), capacity: 16, price: 5999 })
+        .buy_memory(Memory{ model: "Vengeance DDR5 16GB".to_string(), socket: "DDR5".to_string(), capacity: 16, price: 5999 })
+        .buy_storage(Storage{ model: "990 Pro".to_string(), socket: "NVMe".to_string(), price: 11999 })
+        .buy_power_supply(PowerSupply{ model: "Focus GX-450".to_string(), capacity: 450, price: 5999 })
+        .build();
+    if let Ok(pc) = typed_pc {
+        println!("{}", pc.bill_of_materials);
+        let power = &pc.power_budget;
+        if !power.has_headroom() {
+            println!("Warning: the power supply provides {}W, {}W is recommended to keep {}% headroom", power.capacity, power.recommended(), power.headroom_percent);
+        }
+    }
+
     // Singleton pattern
-    exec("INSERT INTO users (name, age) VALUES ('John Doe', 123)".to_string());
+    // Services registered before the pool is first used configure it
+    if let Err(err) = services().register(PoolConfig{ max_size: 8, ..PoolConfig::default() }) {
+        println!("{}", err);
+    }
+    let _ = exec("CREATE TABLE users (name TEXT, age INTEGER)", &[]);
+    if let Err(err) = exec("INSERT INTO users (name, age) VALUES (?, ?)", &["John Doe".into(), Value::Int(123)]) {
+        println!("{}", err);
+    }
+
+    // Queries that belong together run in one transaction, on one connection
+    if let Ok(mut transaction) = begin() {
+        let insert = Statement::prepare("INSERT INTO users (name, age) VALUES (?, ?)").unwrap();
+        for (name, age) in [("Jane O'Brien", Value::Int(31)), ("Bob", Value::Null)] {
+            let _ = transaction.exec_prepared(&insert, &[name.into(), age]);
+        }
+        if let Err(err) = transaction.commit() {
+            println!("{}", err);
+        }
+    }
+    let _ = exec("UPDATE users SET age = ? WHERE age IS NULL", &[Value::Int(45)]);
+    let _ = exec("DELETE FROM users WHERE name = ?", &["John Doe".into()]);
+    // A query that never runs still counts as failed
+    if let Err(err) = exec("SELECT name FROM users WHERE age > ?", &[]) {
+        println!("{}", err);
+    }
+    // A zero threshold counts every query from here on as slow
+    monitor().set_slow_threshold(Duration::ZERO);
+    match exec_prepared(&Statement::prepare("SELECT name, age FROM users WHERE age > ?").unwrap(), &[Value::Int(30)]) {
+        Ok(result) => {
+            for row in 0..result.rows.len() {
+                println!("{:?} is {:?} years old", result.get(row, "name"), result.get(row, "age"));
+            }
+        },
+        Err(err) => println!("{}", err),
+    }
+    println!("Shared pool: {:?}", pool().status());
+    let stats = monitor().stats();
+    for (kind, kind_stats) in stats.by_kind.iter() {
+        println!("{}: {} queries, {} failed, {:?} in total", kind, kind_stats.count, kind_stats.errors, kind_stats.total_time);
+    }
+    println!("Waited {:?} over {} checkouts", stats.total_wait, stats.checkouts);
+    for query in monitor().slow_queries() {
+        println!("Slow query: {} took {:?}", query.sql, query.duration);
+    }
+
+    // The pool can be tried out against an in-memory backend
+    let backend = MemoryBackend::new();
+    let pool = Pool::new(Box::new(backend.clone()), PoolConfig{ max_size: 1, checkout_timeout: Duration::from_millis(10), ..PoolConfig::default() });
+    {
+        let mut connection = pool.get().unwrap();
+        let _ = connection.execute(&Statement::prepare("CREATE TABLE orders (id INTEGER, total REAL)").unwrap(), &[]);
+        if let Err(err) = pool.get() {
+            println!("Second checkout: {}", err);
+        }
+    }
+    backend.break_connections();
+    if let Ok(mut connection) = pool.get() {
+        let _ = connection.execute(&Statement::prepare("SELECT * FROM orders").unwrap(), &[]);
+    }
+    println!("{:?}, {} connections opened", pool.status(), backend.opened());
+
+    // Every query can be sent to the fake pool instead, and a reset starts over with a new database
+    replace_pool(pool);
+    if let Ok(result) = exec("SELECT * FROM orders", &[]) {
+        println!("Orders has columns {:?}", result.columns);
+    }
+    reset_pool();
+    if let Err(err) = exec("SELECT * FROM users", &[]) {
+        println!("After a reset: {}", err);
+    }
+    services().reset();
+
+    // Prototype pattern
+    let mut prototypes = PrototypeRegistry::new();
+    prototypes.register("guest", Box::new(prototype::User{ name: "Guest".to_string(), age: 18 }));
+    prototypes.register("core-team", Box::new(Team{ name: "Core".to_string(), members: vec![prototype::User{ name: "Alice".to_string(), age: 34 }, prototype::User{ name: "Bob".to_string(), age: 27 }] }));
+
+    // Every copy starts out as the prototype, overrides only change the copy
+    if let Ok(user) = prototypes.create_with("guest", |user: &mut prototype::User| user.name = "Jane".to_string()) {
+        println!("{}", user.describe());
+    }
+    if let Ok(mut team) = prototypes.create_as::<Team>("core-team") {
+        team.members.push(prototype::User{ name: "Carol".to_string(), age: 41 });
+        println!("{}", team.describe());
+    }
+    for name in prototypes.names() {
+        if let Ok(copy) = prototypes.create(name) {
+            println!("{}: {}", name, copy.describe());
+        }
+    }
+    if let Err(err) = prototypes.create_as::<prototype::User>("core-team") {
+        println!("{}", err);
+    }
 
     /* Structural Patterns */
     